@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use clap::Parser;
 
 use crate::{BANNER, cmd, service::Services};
@@ -8,6 +9,11 @@ use crate::{BANNER, cmd, service::Services};
 pub struct Cli {
     #[command(subcommand)]
     pub cmd: Option<cmd::Cmd>,
+
+    /// Pretend today is this date (YYYY-MM-DD) without rolling todos over;
+    /// for debugging
+    #[clap(long, global = true, value_name = "DATE")]
+    pub today: Option<NaiveDate>,
 }
 
 impl Default for Cli {
@@ -18,7 +24,7 @@ impl Default for Cli {
 
 impl Cli {
     pub async fn exec(self) -> miette::Result<()> {
-        let services = Services::bootstrap().await?;
+        let services = Services::bootstrap(self.today).await?;
 
         match self.cmd {
             Some(cmd) => cmd.exec(&services).await,
@@ -1,40 +1,152 @@
-use crate::service::Services;
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
 
-/// Add a new todo
+use miette::{IntoDiagnostic, WrapErr, bail};
+
+use crate::{
+    dates::parse_date,
+    service::{Services, todo::MovePlacement},
+};
+
+/// Add new todos
 #[derive(clap::Args)]
 pub struct Args {
     /// Insert the todo into the backlog
     #[clap(short, long, default_value = "false")]
     some_day: bool,
 
-    /// Title of the todo (quoted or space separated)
-    #[clap(required = true)]
+    /// Backlog column, by name or number from 1 (implies --some-day)
+    #[clap(short, long, value_name = "N|NAME", conflicts_with = "on")]
+    column: Option<String>,
+
+    /// Schedule for a date instead of today (e.g. `fri`, `2025-03-05`)
+    #[clap(long, value_name = "DATE", conflicts_with = "some_day")]
+    on: Option<String>,
+
+    /// Notes for the todo
+    #[clap(short, long, conflicts_with = "notes_file")]
+    notes: Option<String>,
+
+    /// Read the notes from a file
+    #[clap(long, value_name = "PATH")]
+    notes_file: Option<PathBuf>,
+
+    /// Put the todo at the top of its column (the default)
+    #[clap(long, conflicts_with = "bottom")]
+    top: bool,
+
+    /// Put the todo at the bottom of its column
+    #[clap(long)]
+    bottom: bool,
+
+    /// Title of the todo (quoted or space separated). Without one, titles
+    /// are read from stdin, one todo per line.
     title: Vec<String>,
 }
 
 impl Args {
     pub async fn exec(self, services: &Services) -> miette::Result<()> {
-        let scheduled_for = if self.some_day {
-            None
+        let titles = self.titles()?;
+        let notes = self.notes()?;
+
+        let column = match &self.column {
+            Some(column) => Some(services.backlog.resolve_column(column).await?),
+            None => None,
+        };
+
+        let scheduled_for = match &self.on {
+            _ if self.some_day || column.is_some() => None,
+            Some(input) => match parse_date(input, services.today()) {
+                Some(date) => Some(date),
+                None => bail!("unrecognized date '{input}'"),
+            },
+            None => Some(services.today()),
+        };
+
+        let placement = if self.bottom {
+            MovePlacement::Bottom
         } else {
-            Some(services.today())
+            MovePlacement::Top
         };
 
-        let todo = services
+        let added = services
             .todos
-            .add(self.title(), scheduled_for, None)
+            .add_many(
+                &titles,
+                scheduled_for,
+                column.map(|column| column as i64),
+                notes,
+                placement,
+            )
             .await?;
 
-        let date_label = scheduled_for
-            .map(|d| d.to_string())
-            .unwrap_or_else(|| "Someday".into());
+        let label = match (scheduled_for, column) {
+            (Some(date), _) => date.to_string(),
+            (None, Some(column)) => {
+                let columns = services.backlog.columns().await?;
+
+                format!("Someday / {}", columns[column])
+            }
+            (None, None) => "Someday".into(),
+        };
 
-        println!("Added todo '{}' -> {}", todo.title, date_label);
+        for todo in added {
+            println!("Added todo '{}' -> {}", todo.title, label);
+        }
 
         Ok(())
     }
 
-    fn title(&self) -> String {
-        self.title.join(" ")
+    /// The title from the arguments, or one per non-blank line of stdin.
+    fn titles(&self) -> miette::Result<Vec<String>> {
+        if !self.title.is_empty() {
+            let title = self.title.join(" ");
+
+            if title.trim().is_empty() {
+                bail!("title must not be empty");
+            }
+
+            return Ok(vec![title]);
+        }
+
+        let mut stdin = std::io::stdin();
+
+        if stdin.is_terminal() {
+            bail!("no title given; pass one or pipe titles on stdin, one per line");
+        }
+
+        let mut input = String::new();
+
+        stdin
+            .read_to_string(&mut input)
+            .into_diagnostic()
+            .wrap_err("failed to read titles from stdin")?;
+
+        let titles: Vec<String> = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        if titles.is_empty() {
+            bail!("no titles on stdin");
+        }
+
+        Ok(titles)
+    }
+
+    fn notes(&self) -> miette::Result<Option<String>> {
+        let notes = match (&self.notes, &self.notes_file) {
+            (Some(notes), _) => notes.clone(),
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("failed to read notes from {}", path.display()))?,
+            (None, None) => return Ok(None),
+        };
+
+        let notes = notes.trim_end();
+
+        Ok((!notes.is_empty()).then(|| notes.to_string()))
     }
 }
@@ -0,0 +1,123 @@
+use miette::IntoDiagnostic;
+
+use crate::service::{
+    Services,
+    agenda::{Agenda, AgendaItem},
+};
+
+use super::list::short_id;
+
+/// Summarize today, rolled-over and upcoming todos
+#[derive(clap::Args)]
+pub struct Args {
+    /// Output format: text, markdown or json
+    #[clap(long, default_value = "text", value_parser = ["text", "markdown", "json"])]
+    format: String,
+}
+
+impl Args {
+    pub async fn exec(self, services: &Services) -> miette::Result<()> {
+        let agenda = services
+            .todos
+            .agenda(services.today(), services.week_start())
+            .await?;
+
+        let output = match self.format.as_str() {
+            "json" => serde_json::to_string_pretty(&agenda).into_diagnostic()? + "\n",
+            "markdown" => markdown(&agenda),
+            _ => text(&agenda),
+        };
+
+        print!("{output}");
+
+        Ok(())
+    }
+}
+
+fn sections(agenda: &Agenda) -> [(&'static str, &[AgendaItem]); 4] {
+    [
+        ("Today", &agenda.today),
+        ("Rolled over", &agenda.rolled_over),
+        ("Upcoming this week", &agenda.upcoming),
+        ("Done yesterday", &agenda.done_yesterday),
+    ]
+}
+
+fn text(agenda: &Agenda) -> String {
+    let mut out = format!("Agenda for {}\n", agenda.date.format("%a %Y-%m-%d"));
+
+    for (heading, items) in sections(agenda) {
+        out.push_str(&format!("\n{heading}\n"));
+
+        if items.is_empty() {
+            out.push_str("  (none)\n");
+        }
+
+        for item in items {
+            let check = if item.is_done() { "x" } else { " " };
+
+            out.push_str(&format!(
+                "  [{check}] {}  {}{}\n",
+                short_id(&item.id),
+                item.title,
+                details(item, heading)
+            ));
+        }
+    }
+
+    out
+}
+
+fn markdown(agenda: &Agenda) -> String {
+    let mut out = format!("# Agenda for {}\n", agenda.date.format("%a %Y-%m-%d"));
+
+    for (heading, items) in sections(agenda) {
+        out.push_str(&format!("\n## {heading}\n\n"));
+
+        if items.is_empty() {
+            out.push_str("_None._\n");
+        }
+
+        for item in items {
+            let check = if item.is_done() { "x" } else { " " };
+
+            out.push_str(&format!(
+                "- [{check}] {}{}\n",
+                item.title,
+                details(item, heading)
+            ));
+        }
+    }
+
+    out
+}
+
+/// Day and slip information shown after the title.
+fn details(item: &AgendaItem, heading: &str) -> String {
+    let mut parts = Vec::new();
+
+    if heading == "Upcoming this week"
+        && let Some(date) = item.scheduled_for
+    {
+        parts.push(date.format("%a %m/%d").to_string());
+    }
+
+    if item.rollover_count > 0 && !item.is_done() {
+        let times = if item.rollover_count == 1 {
+            "once".to_string()
+        } else {
+            format!("{} times", item.rollover_count)
+        };
+
+        match item.slipped_days {
+            Some(days) => parts.push(format!("slipped {times}, {days}d late")),
+            None => parts.push(format!("slipped {times}")),
+        }
+    }
+
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(", "))
+    }
+}
@@ -0,0 +1,17 @@
+use crate::service::Services;
+use crate::tui::snapshot::View;
+
+use super::week::OutputArgs;
+
+/// Print the backlog columns
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl Args {
+    pub async fn exec(self, services: &Services) -> miette::Result<()> {
+        self.output.print(services, View::Backlog).await
+    }
+}
@@ -0,0 +1,163 @@
+use chrono::NaiveDate;
+use miette::bail;
+use uuid::Uuid;
+
+use crate::{
+    dates::parse_date,
+    service::{
+        Services,
+        todo::{ListScope, MovePlacement, tag_edits},
+    },
+};
+
+use super::{filter::FilterArgs, list::print_table};
+
+/// Apply an action to every todo matching a filter
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// Show what would change without applying it
+    #[clap(long, global = true, default_value = "false")]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    action: Action,
+}
+
+#[derive(clap::Subcommand)]
+enum Action {
+    /// Mark the todos as done
+    Done,
+    /// Move the todos to a date
+    Move {
+        /// Target date (YYYY-MM-DD, today, tomorrow, fri, +3d, ...)
+        date: String,
+    },
+    /// Send the todos to the backlog
+    Backlog {
+        /// Backlog column to place them in, by name or number from 1
+        #[clap(long, value_name = "COLUMN")]
+        column: Option<String>,
+    },
+    /// Delete the todos
+    Delete,
+    /// Add (+tag or tag) or remove (-tag) tags
+    Tag {
+        #[clap(required = true, allow_hyphen_values = true)]
+        tags: Vec<String>,
+    },
+}
+
+impl Action {
+    fn describe(&self) -> String {
+        match self {
+            Action::Done => "mark done".into(),
+            Action::Move { date } => format!("move to {date}"),
+            Action::Backlog { column: Some(col) } => format!("send to backlog column {col}"),
+            Action::Backlog { column: None } => "send to backlog".into(),
+            Action::Delete => "delete".into(),
+            Action::Tag { tags } => format!("tag {}", tags.join(" ")),
+        }
+    }
+}
+
+/// An action with its arguments checked, so a dry run rejects the same
+/// input the real run would.
+enum Resolved {
+    Done,
+    Move(NaiveDate),
+    Backlog(Option<i64>),
+    Delete,
+    Tag(Vec<String>),
+}
+
+impl Action {
+    async fn resolve(&self, services: &Services) -> miette::Result<Resolved> {
+        Ok(match self {
+            Action::Done => Resolved::Done,
+            Action::Move { date } => match parse_date(date, services.today()) {
+                Some(date) => Resolved::Move(date),
+                None => bail!("unrecognized date '{date}'"),
+            },
+            Action::Backlog { column } => {
+                let column = match column {
+                    Some(column) => Some(services.backlog.resolve_column(column).await? as i64),
+                    None => None,
+                };
+
+                Resolved::Backlog(column)
+            }
+            Action::Delete => Resolved::Delete,
+            Action::Tag { tags } => {
+                tag_edits(tags)?;
+
+                Resolved::Tag(tags.clone())
+            }
+        })
+    }
+}
+
+impl Args {
+    pub async fn exec(self, services: &Services) -> miette::Result<()> {
+        let query = self.filter.to_query(services)?;
+
+        if query.is_unfiltered() {
+            bail!("refusing to apply to every todo; add at least one filter");
+        }
+
+        let action = self.action.resolve(services).await?;
+        let todos = services.todos.query(&query).await?;
+
+        if todos.is_empty() {
+            println!("No todos matched.");
+
+            return Ok(());
+        }
+
+        let ids: Vec<Uuid> = todos.iter().map(|todo| todo.id).collect();
+
+        if self.dry_run {
+            print_table(&todos);
+
+            println!(
+                "\nWould {} {} todo(s). Re-run without --dry-run to apply.",
+                self.action.describe(),
+                ids.len()
+            );
+
+            return Ok(());
+        }
+
+        let todos_svc = &services.todos;
+
+        match action {
+            Resolved::Done => {
+                todos_svc.complete_many(&ids, services.today()).await?;
+            }
+            Resolved::Move(date) => {
+                todos_svc
+                    .move_many_to_scope(&ids, ListScope::Day(date), MovePlacement::Top)
+                    .await?;
+            }
+            Resolved::Backlog(column) => {
+                todos_svc.move_many_to_backlog(&ids, column).await?;
+            }
+            Resolved::Delete => {
+                todos_svc.delete_many(&ids).await?;
+            }
+            Resolved::Tag(tags) => {
+                todos_svc.retag_many(&ids, &tags).await?;
+            }
+        }
+
+        println!(
+            "Applied '{}' to {} todo(s).",
+            self.action.describe(),
+            ids.len()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::service::{Services, settings};
+
+/// Show or change preferences
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    action: Action,
+}
+
+#[derive(clap::Subcommand)]
+enum Action {
+    /// List every setting with its value and description
+    List,
+    /// Print one setting's value
+    Get { key: String },
+    /// Change a setting
+    Set {
+        key: String,
+        #[clap(allow_hyphen_values = true)]
+        value: String,
+    },
+    /// Go back to a setting's default
+    Reset { key: String },
+}
+
+impl Args {
+    pub async fn exec(self, services: &Services) -> miette::Result<()> {
+        let config = &services.config;
+
+        match self.action {
+            Action::List => {
+                let entries = config.entries().await?;
+
+                let key_width = entries.iter().map(|e| e.def.key.len()).max().unwrap_or(0);
+                let value_width = entries
+                    .iter()
+                    .map(|e| e.display().chars().count())
+                    .max()
+                    .unwrap_or(0);
+
+                for entry in entries {
+                    let marker = if entry.is_set { "*" } else { " " };
+
+                    println!(
+                        "{:<key_width$}  {:<value_width$} {marker} {}",
+                        entry.def.key,
+                        entry.display(),
+                        entry.def.description,
+                    );
+                }
+            }
+            Action::Get { key } => {
+                let def = settings::lookup(&key)?;
+
+                match config.get(&key).await? {
+                    Some(value) => println!("{}", def.format(&value)),
+                    None => println!(),
+                }
+            }
+            Action::Set { key, value } => {
+                let value = config.set(&key, &value).await?;
+
+                println!("{key} = {}", settings::lookup(&key)?.format(&value));
+            }
+            Action::Reset { key } => {
+                config.reset(&key).await?;
+
+                println!("Reset {key}");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use miette::bail;
+
+use crate::{editor, service::Services};
+
+/// Edit an existing todo
+#[derive(clap::Args)]
+pub struct Args {
+    /// Id of the todo (or a unique prefix, as shown by `mach list`)
+    id: String,
+
+    /// Edit the notes in $VISUAL / $EDITOR
+    #[clap(short, long, default_value = "false")]
+    notes: bool,
+
+    /// Replace the title
+    #[clap(short, long)]
+    title: Option<String>,
+}
+
+impl Args {
+    pub async fn exec(self, services: &Services) -> miette::Result<()> {
+        if !self.notes && self.title.is_none() {
+            bail!("nothing to edit; pass --notes or --title");
+        }
+
+        let id = services.todos.resolve_id(&self.id).await?;
+
+        let mut todo = services.todos.get(id).await?;
+
+        if let Some(title) = self.title {
+            let title = title.trim().to_string();
+
+            if title.is_empty() {
+                bail!("title must not be empty");
+            }
+
+            todo = services.todos.update_title(id, title).await?;
+        }
+
+        if self.notes {
+            let current = todo.notes.clone().unwrap_or_default();
+            let edited = tokio::task::spawn_blocking(move || editor::edit_text(&current))
+                .await
+                .map_err(|err| miette::miette!("editor task failed: {err}"))??;
+
+            todo = services
+                .todos
+                .update_notes(id, editor::notes_from_editor(edited))
+                .await?;
+        }
+
+        println!("Updated todo '{}'", todo.title);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use miette::bail;
+
+use crate::{
+    dates::parse_date,
+    service::{
+        Services,
+        todo::{PlacementQuery, StatusQuery, TodoQuery},
+    },
+};
+
+/// Flags shared by commands that select a set of todos.
+#[derive(clap::Args)]
+pub struct FilterArgs {
+    /// Query expression, e.g. "status:pending tag:work due<fri"
+    #[clap(short, long, value_name = "EXPR")]
+    query: Option<String>,
+
+    /// Status to match: pending, done or any
+    #[clap(long, value_parser = ["pending", "done", "any"])]
+    status: Option<String>,
+
+    /// Scheduled on or after this date (YYYY-MM-DD, today, fri, -7d, ...)
+    #[clap(long)]
+    from: Option<String>,
+
+    /// Scheduled on or before this date
+    #[clap(long)]
+    to: Option<String>,
+
+    /// Require a tag (repeatable)
+    #[clap(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Case-insensitive regex the title must match
+    #[clap(long, value_name = "REGEX")]
+    title: Option<String>,
+
+    /// Only backlog todos
+    #[clap(long, conflicts_with_all = ["from", "to"])]
+    backlog: bool,
+
+    /// Only todos in this backlog column, counted from 1 (implies --backlog)
+    #[clap(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(i64).range(1..),
+        conflicts_with_all = ["from", "to"]
+    )]
+    column: Option<i64>,
+}
+
+impl FilterArgs {
+    /// Use `expr` as the query expression, as if passed via `--query`.
+    pub fn set_query(&mut self, expr: String) {
+        self.query = Some(expr);
+    }
+
+    pub fn to_query(&self, services: &Services) -> miette::Result<TodoQuery> {
+        self.narrow(TodoQuery::new(), services)
+    }
+
+    /// Apply the filters on top of `query`, overriding its defaults.
+    pub fn narrow(&self, query: TodoQuery, services: &Services) -> miette::Result<TodoQuery> {
+        let today = services.today();
+
+        let mut query = match &self.query {
+            Some(expr) => query.with_terms(expr, today, services.week_start())?,
+            None => query,
+        };
+
+        query = match self.status.as_deref() {
+            Some("pending") => query.status(StatusQuery::Pending),
+            Some("done") => query.status(StatusQuery::Done),
+            Some("any") => query.status(StatusQuery::Any),
+            _ => query,
+        };
+
+        if let Some(from) = &self.from {
+            let Some(date) = parse_date(from, today) else {
+                bail!("unrecognized --from date '{from}'");
+            };
+
+            query = query.from(date);
+        }
+
+        if let Some(to) = &self.to {
+            let Some(date) = parse_date(to, today) else {
+                bail!("unrecognized --to date '{to}'");
+            };
+
+            query = query.to(date);
+        }
+
+        for tag in &self.tags {
+            query = query.tag(tag);
+        }
+
+        if let Some(pattern) = &self.title {
+            query = query.title_matches(pattern)?;
+        }
+
+        if self.backlog || self.column.is_some() {
+            let column = self.column.map(|number| number - 1);
+
+            query = query.placement(PlacementQuery::Backlog(column));
+        }
+
+        Ok(query)
+    }
+}
@@ -1,11 +1,30 @@
+use chrono::Duration;
+use miette::bail;
+
+use crate::dates::{parse_offset, start_of_week};
+use crate::entity::todo;
 use crate::service::{
     Services,
-    todo::{ListOptions, ListScope},
+    query::parse_sort,
+    todo::{ListOptions, ListScope, PlacementQuery, StatusQuery, TodoQuery},
 };
 
-/// List all todos in a table
+use super::filter::FilterArgs;
+
+/// List todos in a table
+///
+/// Without filters, lists today's todos. Any filter switches to a query
+/// across all days and the backlog. Completed todos are left out unless
+/// `--done` or a status filter asks for them.
 #[derive(clap::Args)]
 pub struct Args {
+    /// Query expression, e.g. `status:pending tag:work due<fri`
+    #[clap(conflicts_with = "query")]
+    words: Vec<String>,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
     /// List todos in the backlog
     #[clap(short, long, default_value = "false")]
     some_day: bool,
@@ -13,47 +32,150 @@ pub struct Args {
     /// Include completed todos
     #[clap(short, long, default_value = "false")]
     done: bool,
+
+    /// Only this week's todos
+    #[clap(long, conflicts_with_all = ["overdue", "upcoming"])]
+    week: bool,
+
+    /// Only pending todos scheduled before today
+    #[clap(long, conflicts_with = "upcoming")]
+    overdue: bool,
+
+    /// Only todos due within this span from today (e.g. 7d, 2w)
+    #[clap(long, value_name = "SPAN")]
+    upcoming: Option<String>,
+
+    /// Text to find in titles and notes
+    #[clap(long, value_name = "TEXT")]
+    search: Option<String>,
+
+    /// Sort by day, title, created or status
+    #[clap(long, value_name = "KEY")]
+    sort: Option<String>,
+
+    /// Show at most this many todos
+    #[clap(long, value_name = "N")]
+    limit: Option<usize>,
 }
 
 impl Args {
-    pub async fn exec(self, services: &Services) -> miette::Result<()> {
-        let scope = if self.some_day {
-            ListScope::Backlog
+    pub async fn exec(mut self, services: &Services) -> miette::Result<()> {
+        let today = services.today();
+
+        if !self.words.is_empty() {
+            self.filter.set_query(self.words.join(" "));
+        }
+
+        // CLI views leave completed todos out unless asked for.
+        let status = if self.done {
+            StatusQuery::Any
         } else {
-            ListScope::Day(services.today())
+            StatusQuery::Pending
         };
 
-        let opts = ListOptions {
-            scope,
-            include_done: self.done,
-        };
+        let mut query = self
+            .filter
+            .narrow(TodoQuery::new().status(status), services)?;
 
-        let todos = services.todos.list(opts).await?;
+        if self.week {
+            let start = start_of_week(today, services.week_start());
 
-        if todos.is_empty() {
-            println!("No todos found.");
+            query = query.from(start).to(start + Duration::days(6));
+        }
 
-            return Ok(());
+        if self.overdue {
+            query = query
+                .status(StatusQuery::Pending)
+                .to(today - Duration::days(1));
+        }
+
+        if let Some(span) = &self.upcoming {
+            let Some(days) = parse_offset(span).filter(|days| *days >= 0) else {
+                bail!("invalid --upcoming span '{span}'");
+            };
+
+            query = query.from(today).to(today + Duration::days(days));
+        }
+
+        if let Some(text) = &self.search {
+            query = query.search(text);
         }
 
-        println!("{:<8} {:<12} Title", "Status", "Day");
-        println!("{}", "-".repeat(48));
+        if let Some(key) = &self.sort {
+            query = query.sort(parse_sort(key)?);
+        }
+
+        if let Some(limit) = self.limit {
+            query = query.limit(limit);
+        }
 
-        for todo in todos {
-            let day = todo
-                .scheduled_for
-                .map(|d| d.to_string())
-                .unwrap_or_else(|| "Someday".to_string());
+        // Sorting and limiting alone keep today's list (or the backlog).
+        let scoped =
+            query.status == status && query.clone().status(StatusQuery::Any).is_unfiltered();
 
-            let status = if todo.status == "done" {
-                "done"
+        if scoped {
+            let scope = if self.some_day {
+                ListScope::Backlog
             } else {
-                "pending"
+                ListScope::Day(today)
             };
 
-            println!("{:<8} {:<12} {}", status, day, todo.title);
+            let opts = ListOptions {
+                scope,
+                include_done: self.done,
+            };
+
+            let mut scoped: TodoQuery = opts.into();
+
+            scoped.sort = query.sort;
+            scoped.limit = query.limit;
+            query = scoped;
+        } else if self.some_day && query.placement == PlacementQuery::Anywhere {
+            query = query.placement(PlacementQuery::Backlog(None));
+        }
+
+        let todos = services.todos.query(&query).await?;
+
+        if todos.is_empty() {
+            println!("No todos found.");
+
+            return Ok(());
         }
 
+        print_table(&todos);
+
         Ok(())
     }
 }
+
+/// Print todos as the table used by `mach list`.
+pub fn print_table(todos: &[todo::Model]) {
+    println!("{:<8} {:<8} {:<12} Title", "ID", "Status", "Day");
+    println!("{}", "-".repeat(57));
+
+    for todo in todos {
+        let day = todo
+            .scheduled_for
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "Someday".to_string());
+
+        let status = if todo.status == "done" {
+            "done"
+        } else {
+            "pending"
+        };
+
+        println!(
+            "{:<8} {:<8} {:<12} {}",
+            short_id(&todo.id),
+            status,
+            day,
+            todo.title
+        );
+    }
+}
+
+/// First block of the id, enough to pass to commands like `mach edit`.
+pub fn short_id(id: &uuid::Uuid) -> String {
+    id.to_string()[..8].to_string()
+}
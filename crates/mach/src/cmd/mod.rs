@@ -1,17 +1,36 @@
 pub mod add;
+pub mod agenda;
+pub mod backlog;
+pub mod bulk;
+pub mod config;
+pub mod edit;
+pub mod filter;
 pub mod list;
+pub mod week;
 
 #[derive(clap::Subcommand)]
 pub enum Cmd {
     Add(add::Args),
+    Agenda(agenda::Args),
+    Backlog(backlog::Args),
+    Bulk(bulk::Args),
+    Config(config::Args),
+    Edit(edit::Args),
     List(list::Args),
+    Week(week::Args),
 }
 
 impl Cmd {
     pub async fn exec(self, services: &crate::service::Services) -> miette::Result<()> {
         match self {
             Cmd::Add(args) => args.exec(services).await,
+            Cmd::Agenda(args) => args.exec(services).await,
+            Cmd::Backlog(args) => args.exec(services).await,
+            Cmd::Bulk(args) => args.exec(services).await,
+            Cmd::Config(args) => args.exec(services).await,
+            Cmd::Edit(args) => args.exec(services).await,
             Cmd::List(args) => args.exec(services).await,
+            Cmd::Week(args) => args.exec(services).await,
         }
     }
 }
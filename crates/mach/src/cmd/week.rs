@@ -0,0 +1,83 @@
+use std::io::{IsTerminal, stdout};
+
+use chrono::Duration;
+use miette::bail;
+
+use crate::dates::parse_date;
+use crate::service::Services;
+use crate::tui::snapshot::{self, View};
+
+/// Print the weekly board
+#[derive(clap::Args)]
+pub struct Args {
+    /// Show next week
+    #[clap(long, conflicts_with_all = ["prev", "date"])]
+    next: bool,
+
+    /// Show last week
+    #[clap(long, conflicts_with = "date")]
+    prev: bool,
+
+    /// Show the week containing this date (YYYY-MM-DD, fri, +2w, ...)
+    #[clap(long, value_name = "DATE")]
+    date: Option<String>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl Args {
+    pub async fn exec(self, services: &Services) -> miette::Result<()> {
+        let today = services.today();
+
+        let date = match &self.date {
+            Some(input) => {
+                let Some(date) = parse_date(input, today) else {
+                    bail!("unrecognized date '{input}'");
+                };
+
+                date
+            }
+            None if self.next => today + Duration::days(7),
+            None if self.prev => today - Duration::days(7),
+            None => today,
+        };
+
+        self.output.print(services, View::Week(date)).await
+    }
+}
+
+/// Output flags shared by `mach week` and `mach backlog`.
+#[derive(clap::Args)]
+pub struct OutputArgs {
+    /// Disable colors (the default when stdout is not a terminal)
+    #[clap(long)]
+    plain: bool,
+
+    /// Width in columns (defaults to the terminal width, or 120)
+    #[clap(long, value_name = "N")]
+    width: Option<u16>,
+}
+
+impl OutputArgs {
+    pub async fn print(&self, services: &Services, view: View) -> miette::Result<()> {
+        let is_tty = stdout().is_terminal();
+
+        let width = self
+            .width
+            .or_else(|| {
+                is_tty
+                    .then(|| crossterm::terminal::size().ok().map(|(cols, _)| cols))
+                    .flatten()
+            })
+            .unwrap_or(120);
+
+        let plain = self.plain || !is_tty || std::env::var_os("NO_COLOR").is_some();
+
+        let output = snapshot::render(services.clone(), view, width, plain).await?;
+
+        print!("{output}");
+
+        Ok(())
+    }
+}
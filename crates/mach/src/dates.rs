@@ -0,0 +1,89 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::service::config::WeekStart;
+
+/// Parse a human date expression relative to `today`.
+///
+/// Accepts `YYYY-MM-DD`, `today`, `tomorrow`/`tmr`, `yesterday`, weekday
+/// names (`fri`, `friday`: the next such day, today included), `next <weekday>`
+/// (strictly after this week's), and offsets such as `+3d`, `-1w`, `2w`.
+pub fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_ascii_lowercase();
+
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    match input.as_str() {
+        "today" | "tod" => return Some(today),
+        "tomorrow" | "tmr" | "tom" => return Some(today + Duration::days(1)),
+        "yesterday" => return Some(today - Duration::days(1)),
+        _ => {}
+    }
+
+    if let Some(rest) = input.strip_prefix("next ") {
+        let weekday = parse_weekday(rest.trim())?;
+
+        return Some(upcoming(today, weekday) + Duration::days(7));
+    }
+
+    if let Some(weekday) = parse_weekday(&input) {
+        return Some(upcoming(today, weekday));
+    }
+
+    parse_offset(&input).map(|days| today + Duration::days(days))
+}
+
+/// Parse an offset such as `+3d`, `-2w` or `7d` into a number of days.
+pub fn parse_offset(input: &str) -> Option<i64> {
+    let (sign, rest) = match input.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, input.strip_prefix('+').unwrap_or(input)),
+    };
+
+    let unit_at = rest.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = rest.split_at(unit_at);
+    let amount: i64 = amount.parse().ok()?;
+
+    let days = match unit {
+        "d" | "day" | "days" => amount,
+        "w" | "wk" | "week" | "weeks" => amount * 7,
+        _ => return None,
+    };
+
+    Some(sign * days)
+}
+
+pub fn parse_weekday(input: &str) -> Option<Weekday> {
+    let weekday = match input {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+
+    Some(weekday)
+}
+
+/// The next `weekday` on or after `today`.
+fn upcoming(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+
+    today + Duration::days(ahead as i64)
+}
+
+/// First day of the week containing `date` for the given preference.
+pub fn start_of_week(date: NaiveDate, preference: WeekStart) -> NaiveDate {
+    let weekday = date.weekday();
+
+    let offset = match preference {
+        WeekStart::Sunday => weekday.num_days_from_sunday() as i64,
+        WeekStart::Monday => weekday.num_days_from_monday() as i64,
+    };
+
+    date - Duration::days(offset)
+}
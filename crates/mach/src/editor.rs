@@ -0,0 +1,76 @@
+use std::{env, fs, process::Command};
+
+use miette::{Context, IntoDiagnostic, bail};
+use uuid::Uuid;
+
+/// Open `initial` in the user's editor and return the saved contents.
+///
+/// The editor is taken from `$VISUAL`, then `$EDITOR`, falling back to `vi`
+/// (`notepad` on Windows). The variable is split like a shell would, so
+/// arguments (`code --wait`) and quoted paths with spaces both work.
+pub fn edit_text(initial: &str) -> miette::Result<String> {
+    let path = env::temp_dir().join(format!("mach-{}.md", Uuid::new_v4()));
+
+    fs::write(&path, initial)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to write temp file {}", path.display()))?;
+
+    let result = run_editor(&path).and_then(|()| {
+        fs::read_to_string(&path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("failed to read temp file {}", path.display()))
+    });
+
+    let _ = fs::remove_file(&path);
+
+    result
+}
+
+fn run_editor(path: &std::path::Path) -> miette::Result<()> {
+    let editor = editor_command();
+    let Some(parts) = shlex::split(&editor) else {
+        bail!("couldn't parse editor command `{editor}`; check its quoting");
+    };
+
+    let Some((program, args)) = parts.split_first() else {
+        bail!("no editor configured; set $VISUAL or $EDITOR");
+    };
+
+    let status = Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to launch editor `{editor}`"))?;
+
+    if !status.success() {
+        bail!("editor `{editor}` exited with {status}");
+    }
+
+    Ok(())
+}
+
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".into()
+            } else {
+                "vi".into()
+            }
+        })
+}
+
+/// Normalize editor output into the optional notes stored on a todo.
+pub fn notes_from_editor(contents: String) -> Option<String> {
+    let trimmed = contents.trim_end();
+
+    if trimmed.trim().is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
@@ -23,13 +23,17 @@ impl ActiveModelBehavior for ActiveModel {
     where
         C: ConnectionTrait,
     {
+        // Services stamp times from their clock; fall back to the wall clock
+        // for anything saved without one.
         let now = Utc::now();
 
         if self.created_at.is_not_set() {
             self.created_at = Set(now);
         }
 
-        self.updated_at = Set(now);
+        if !self.updated_at.is_set() {
+            self.updated_at = Set(now);
+        }
 
         Ok(self)
     }
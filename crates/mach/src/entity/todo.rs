@@ -27,19 +27,110 @@ pub struct Model {
     pub metadata: JsonValue,
 }
 
+impl Model {
+    /// Tags stored under `metadata.tags`.
+    pub fn tags(&self) -> Vec<String> {
+        self.metadata
+            .get("tags")
+            .and_then(JsonValue::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|tag| tag.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// How many times rollover has carried this todo to a later day
+    /// (`metadata.rollover_count`).
+    pub fn rollover_count(&self) -> u64 {
+        self.metadata
+            .get("rollover_count")
+            .and_then(JsonValue::as_u64)
+            .unwrap_or(0)
+    }
+
+    /// The day this todo was scheduled for before it first rolled over
+    /// (`metadata.rolled_from`).
+    pub fn rolled_from(&self) -> Option<Date> {
+        self.metadata
+            .get("rolled_from")
+            .and_then(JsonValue::as_str)
+            .and_then(|date| date.parse().ok())
+    }
+
+    /// The day rollover last carried this todo onto (`metadata.rolled_on`).
+    pub fn rolled_on(&self) -> Option<Date> {
+        self.metadata
+            .get("rolled_on")
+            .and_then(JsonValue::as_str)
+            .and_then(|date| date.parse().ok())
+    }
+}
+
+/// Return `metadata` with `tags` replaced, preserving any other keys.
+pub fn with_tags(metadata: JsonValue, tags: &[String]) -> JsonValue {
+    let tags = (!tags.is_empty()).then(|| JsonValue::from(tags.to_vec()));
+
+    with_key(metadata, "tags", tags)
+}
+
+/// Return `metadata` after one more rollover away from `from` onto `on`,
+/// keeping the original day if the todo has rolled over before.
+pub fn with_rollover(metadata: JsonValue, from: Date, on: Date) -> JsonValue {
+    let count = metadata
+        .get("rollover_count")
+        .and_then(JsonValue::as_u64)
+        .unwrap_or(0);
+
+    let rolled_from = metadata
+        .get("rolled_from")
+        .cloned()
+        .unwrap_or_else(|| JsonValue::from(from.to_string()));
+
+    let metadata = with_key(metadata, "rollover_count", Some(JsonValue::from(count + 1)));
+
+    let metadata = with_key(metadata, "rolled_from", Some(rolled_from));
+
+    with_key(metadata, "rolled_on", Some(JsonValue::from(on.to_string())))
+}
+
+/// Set or remove a single metadata key, preserving the others.
+fn with_key(metadata: JsonValue, key: &str, value: Option<JsonValue>) -> JsonValue {
+    let mut object = match metadata {
+        JsonValue::Object(object) => object,
+        _ => serde_json::Map::new(),
+    };
+
+    match value {
+        Some(value) => object.insert(key.into(), value),
+        None => object.remove(key),
+    };
+
+    if object.is_empty() {
+        JsonValue::Null
+    } else {
+        JsonValue::Object(object)
+    }
+}
+
 #[async_trait]
 impl ActiveModelBehavior for ActiveModel {
     async fn before_save<C>(mut self, _db: &C, _insert: bool) -> Result<Self, sea_orm::DbErr>
     where
         C: ConnectionTrait,
     {
+        // Services stamp times from their clock; fall back to the wall clock
+        // for anything saved without one.
         let now = Utc::now();
 
         if self.created_at.is_not_set() {
             self.created_at = Set(now);
         }
 
-        self.updated_at = Set(now);
+        if !self.updated_at.is_set() {
+            self.updated_at = Set(now);
+        }
 
         if self.metadata.is_not_set() {
             self.metadata = Set(JsonValue::Null);
@@ -1,5 +1,7 @@
 mod cli;
 mod cmd;
+mod dates;
+mod editor;
 pub mod entity;
 pub mod service;
 pub mod tui;
@@ -0,0 +1,116 @@
+//! Daily summary used by `mach agenda`.
+
+use chrono::{Duration, NaiveDate};
+use miette::Result;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::dates::start_of_week;
+use crate::entity::todo;
+
+use super::config::WeekStart;
+use super::todo::{PlacementQuery, StatusQuery, TodoQuery, TodoService};
+
+/// Today's work at a glance.
+#[derive(Debug, Clone, Serialize)]
+pub struct Agenda {
+    pub date: NaiveDate,
+    /// Everything scheduled for today, including items that rolled over.
+    pub today: Vec<AgendaItem>,
+    /// Pending items carried over from earlier days.
+    pub rolled_over: Vec<AgendaItem>,
+    /// Pending items for the rest of this week.
+    pub upcoming: Vec<AgendaItem>,
+    pub done_yesterday: Vec<AgendaItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgendaItem {
+    pub id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub scheduled_for: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    /// How many times rollover has moved this todo.
+    pub rollover_count: u64,
+    /// Days since the todo was originally scheduled, if it rolled over.
+    pub slipped_days: Option<i64>,
+}
+
+impl AgendaItem {
+    fn new(model: todo::Model, today: NaiveDate) -> Self {
+        let slipped_days = model.rolled_from().map(|from| (today - from).num_days());
+
+        Self {
+            rollover_count: model.rollover_count(),
+            tags: model.tags(),
+            id: model.id,
+            title: model.title,
+            status: model.status,
+            scheduled_for: model.scheduled_for,
+            slipped_days,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status == "done"
+    }
+}
+
+impl TodoService {
+    /// Gather the agenda for `today`, with "this week" bounded by `week_start`.
+    pub async fn agenda(&self, today: NaiveDate, week_start: WeekStart) -> Result<Agenda> {
+        let yesterday = today - Duration::days(1);
+        let week_end = start_of_week(today, week_start) + Duration::days(6);
+
+        let item = |model| AgendaItem::new(model, today);
+
+        let today_items: Vec<AgendaItem> = self
+            .query(&TodoQuery::new().from(today).to(today))
+            .await?
+            .into_iter()
+            .map(item)
+            .collect();
+
+        // Only what rollover carried here today, not todos that slipped once
+        // and were rescheduled since.
+        let rolled_over = self
+            .rolled_over_on(today)
+            .await?
+            .into_iter()
+            .map(item)
+            .collect();
+
+        let upcoming = if week_end > today {
+            let query = TodoQuery::new()
+                .status(StatusQuery::Pending)
+                .placement(PlacementQuery::Scheduled)
+                .from(today + Duration::days(1))
+                .to(week_end);
+
+            self.query(&query).await?.into_iter().map(item).collect()
+        } else {
+            Vec::new()
+        };
+
+        let done_yesterday = self
+            .query(
+                &TodoQuery::new()
+                    .status(StatusQuery::Done)
+                    .from(yesterday)
+                    .to(yesterday),
+            )
+            .await?
+            .into_iter()
+            .map(item)
+            .collect();
+
+        Ok(Agenda {
+            date: today,
+            today: today_items,
+            rolled_over,
+            upcoming,
+            done_yesterday,
+        })
+    }
+}
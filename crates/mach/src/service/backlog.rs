@@ -0,0 +1,215 @@
+//! Named backlog columns. The names are stored in config under
+//! `backlog_columns`; todos refer to a column by its index, so reordering or
+//! removing a column renumbers them in the same transaction. Indexes count
+//! from 0 here; people number columns from 1, and [`resolve_column`] and the
+//! messages below translate.
+//!
+//! [`resolve_column`]: BacklogService::resolve_column
+
+use miette::Result;
+use serde_json::{Value as JsonValue, json};
+
+use super::clock::SharedClock;
+use super::config::{load_value, save_value};
+use super::todo::{TodoService, TodoTransaction};
+
+const COLUMNS_KEY: &str = "backlog_columns";
+
+/// Columns used until the user changes them.
+pub const DEFAULT_COLUMNS: [&str; 4] = ["Ideas", "Next", "Waiting", "Later"];
+
+/// More columns than this get too narrow to read.
+pub const MAX_COLUMNS: usize = 8;
+
+/// What happened to the todos of a removed column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedColumn {
+    pub name: String,
+    /// Column the todos moved to, by name.
+    pub into: String,
+    /// Backlog todos that moved.
+    pub moved: usize,
+}
+
+#[derive(Clone)]
+pub struct BacklogService {
+    todos: TodoService,
+    clock: SharedClock,
+}
+
+impl BacklogService {
+    pub fn new(todos: TodoService, clock: SharedClock) -> Self {
+        Self { todos, clock }
+    }
+
+    /// Column names, left to right.
+    pub async fn columns(&self) -> Result<Vec<String>> {
+        let value = load_value(self.todos.connection(), COLUMNS_KEY).await?;
+
+        Ok(parse_columns(value))
+    }
+
+    /// Find a column by name (ignoring case) or by its number counted from 1,
+    /// as in `--column N`, returning its index.
+    pub async fn resolve_column(&self, input: &str) -> Result<usize> {
+        let columns = self.columns().await?;
+        let input = input.trim();
+
+        if let Some(idx) = columns
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(input))
+        {
+            return Ok(idx);
+        }
+
+        match input.parse::<usize>() {
+            Ok(number) if (1..=columns.len()).contains(&number) => Ok(number - 1),
+            _ => Err(miette::miette!(
+                help = format!("columns: {}", columns.join(", ")),
+                "no backlog column '{input}'"
+            )),
+        }
+    }
+
+    /// Append a column, returning its index.
+    pub async fn add_column(&self, name: &str) -> Result<usize> {
+        self.edit(async |_, columns| {
+            if columns.len() >= MAX_COLUMNS {
+                miette::bail!("the backlog has at most {MAX_COLUMNS} columns");
+            }
+
+            let name = check_name(columns, name, None)?;
+
+            columns.push(name);
+
+            Ok(columns.len() - 1)
+        })
+        .await
+    }
+
+    pub async fn rename_column(&self, index: usize, name: &str) -> Result<()> {
+        self.edit(async |_, columns| {
+            check_index(columns, index)?;
+
+            columns[index] = check_name(columns, name, Some(index))?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Move a column to position `to` (clamped), taking its todos along.
+    /// Returns where it ended up.
+    pub async fn move_column(&self, index: usize, to: usize) -> Result<usize> {
+        self.edit(async |txn, columns| {
+            check_index(columns, index)?;
+
+            let to = to.min(columns.len() - 1);
+            let mut order: Vec<i64> = (0..columns.len() as i64).collect();
+
+            let name = columns.remove(index);
+            columns.insert(to, name);
+
+            let old = order.remove(index);
+            order.insert(to, old);
+
+            txn.renumber_backlog_columns(&order).await?;
+
+            Ok(to)
+        })
+        .await
+    }
+
+    /// Remove a column. Its todos move to the bottom of the column to its
+    /// left, or to its right for the first column; the last column can't
+    /// be removed. Removing the rightmost column also takes along todos
+    /// left in columns past it.
+    pub async fn remove_column(&self, index: usize) -> Result<RemovedColumn> {
+        self.edit(async |txn, columns| {
+            check_index(columns, index)?;
+
+            if columns.len() == 1 {
+                miette::bail!("the backlog needs at least one column");
+            }
+
+            let into = if index > 0 { index - 1 } else { 1 };
+            let rightmost = index == columns.len() - 1;
+            let moved = txn
+                .merge_backlog_column(index as i64, into as i64, rightmost)
+                .await?;
+
+            let order: Vec<i64> = (0..columns.len() as i64)
+                .filter(|&col| col != index as i64)
+                .collect();
+
+            txn.renumber_backlog_columns(&order).await?;
+
+            let into = columns[into].clone();
+            let name = columns.remove(index);
+
+            Ok(RemovedColumn { name, into, moved })
+        })
+        .await
+    }
+
+    /// Load the columns, let `f` change them and the todos, and save both
+    /// together.
+    async fn edit<T>(
+        &self,
+        f: impl AsyncFnOnce(&TodoTransaction, &mut Vec<String>) -> Result<T>,
+    ) -> Result<T> {
+        self.todos
+            .transaction(async |txn| {
+                let value = load_value(txn.connection(), COLUMNS_KEY).await?;
+                let mut columns = parse_columns(value);
+
+                let result = f(txn, &mut columns).await?;
+
+                save_value(txn.connection(), &self.clock, COLUMNS_KEY, json!(columns)).await?;
+
+                Ok(result)
+            })
+            .await
+    }
+}
+
+/// Stored names, or the defaults when unset or unreadable.
+fn parse_columns(value: Option<JsonValue>) -> Vec<String> {
+    let columns: Vec<String> = value
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+
+    if columns.is_empty() {
+        DEFAULT_COLUMNS.iter().map(ToString::to_string).collect()
+    } else {
+        columns
+    }
+}
+
+fn check_index(columns: &[String], index: usize) -> Result<()> {
+    if index >= columns.len() {
+        miette::bail!("no backlog column {}", index + 1);
+    }
+
+    Ok(())
+}
+
+/// A trimmed, non-empty name no other column (than `except`) uses.
+fn check_name(columns: &[String], name: &str, except: Option<usize>) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        miette::bail!("column name must not be empty");
+    }
+
+    let taken = columns
+        .iter()
+        .enumerate()
+        .any(|(idx, other)| Some(idx) != except && other.eq_ignore_ascii_case(name));
+
+    if taken {
+        miette::bail!("there's already a column named '{name}'");
+    }
+
+    Ok(name.to_string())
+}
@@ -0,0 +1,114 @@
+//! Time source for everything date-dependent (rollover, today/tomorrow
+//! moves, timestamps), so it can be pinned in tests and via `--today`.
+
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real wall clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Noon on `date` in `zone`, so the services see `date` as today.
+    pub fn on(date: NaiveDate, zone: TimeZonePref) -> Self {
+        let noon = date.and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+
+        let instant = match zone {
+            TimeZonePref::Local => Local
+                .from_local_datetime(&noon)
+                .earliest()
+                .map(|at| at.to_utc()),
+            TimeZonePref::Named(tz) => tz
+                .from_local_datetime(&noon)
+                .earliest()
+                .map(|at| at.to_utc()),
+        };
+
+        Self::new(instant.unwrap_or_else(|| noon.and_utc()))
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// Zone used to decide which calendar day it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeZonePref {
+    /// The system's local zone.
+    #[default]
+    Local,
+    Named(Tz),
+}
+
+impl TimeZonePref {
+    /// The calendar date of `instant` in this zone.
+    pub fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        match self {
+            TimeZonePref::Local => instant.with_timezone(&Local).date_naive(),
+            TimeZonePref::Named(tz) => instant.with_timezone(tz).date_naive(),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeZonePref::Local => "local",
+            TimeZonePref::Named(tz) => tz.name(),
+        }
+    }
+}
+
+impl FromStr for TimeZonePref {
+    type Err = String;
+
+    /// `local` or an IANA zone name such as `Europe/Berlin`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case("local") {
+            return Ok(TimeZonePref::Local);
+        }
+
+        value
+            .parse::<Tz>()
+            .map(TimeZonePref::Named)
+            .map_err(|_| format!("unknown time zone '{value}'"))
+    }
+}
@@ -1,11 +1,17 @@
+use std::collections::{BTreeMap, HashMap};
+
 use crate::entity::config;
-use chrono::Utc;
 use miette::IntoDiagnostic;
 use sea_orm::{
-    ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
     sea_query::OnConflict,
 };
-use serde_json::json;
+use serde_json::Value as JsonValue;
+
+use super::clock::{SharedClock, TimeZonePref};
+use super::settings::{self, SETTINGS, SettingEntry};
+
+pub const DEFAULT_STALE_AFTER: u64 = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WeekStart {
@@ -13,15 +19,6 @@ pub enum WeekStart {
     Monday,
 }
 
-impl WeekStart {
-    pub fn toggle(self) -> Self {
-        match self {
-            WeekStart::Sunday => WeekStart::Monday,
-            WeekStart::Monday => WeekStart::Sunday,
-        }
-    }
-}
-
 impl From<&str> for WeekStart {
     fn from(value: &str) -> Self {
         match value {
@@ -40,51 +37,250 @@ impl WeekStart {
     }
 }
 
+/// How todo titles that don't fit their column are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleOverflow {
+    /// Wrap the title onto as many lines as needed.
+    Wrap,
+    /// Cut the title with an ellipsis and show it in full in the footer.
+    Truncate,
+}
+
+impl TitleOverflow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TitleOverflow::Wrap => "wrap",
+            TitleOverflow::Truncate => "truncate",
+        }
+    }
+}
+
+impl From<&str> for TitleOverflow {
+    fn from(value: &str) -> Self {
+        match value {
+            "truncate" => TitleOverflow::Truncate,
+            _ => TitleOverflow::Wrap,
+        }
+    }
+}
+
+/// Named color scheme for the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// ANSI colors that follow the terminal's own palette.
+    Default,
+    HighContrast,
+    Solarized,
+    /// No colors, only bold/underline/reverse. Forced by `NO_COLOR`.
+    Monochrome,
+}
+
+impl Theme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::HighContrast => "high-contrast",
+            Theme::Solarized => "solarized",
+            Theme::Monochrome => "monochrome",
+        }
+    }
+}
+
+impl From<&str> for Theme {
+    fn from(value: &str) -> Self {
+        match value {
+            "high-contrast" => Theme::HighContrast,
+            "solarized" => Theme::Solarized,
+            "monochrome" | "no-color" => Theme::Monochrome,
+            _ => Theme::Default,
+        }
+    }
+}
+
+/// Prefix of the config keys that override one palette role, e.g.
+/// `color.focus = "cyan"`.
+pub const COLOR_KEY_PREFIX: &str = "color.";
+
 #[derive(Clone)]
 pub struct ConfigService {
     db: DatabaseConnection,
+    clock: SharedClock,
 }
 
 impl ConfigService {
-    pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+    pub fn new(db: DatabaseConnection, clock: SharedClock) -> Self {
+        Self { db, clock }
     }
 
     pub async fn load_week_start(&self) -> miette::Result<WeekStart> {
-        let result = config::Entity::find()
-            .filter(config::Column::Key.eq("week_start"))
-            .one(&self.db)
+        Ok(self
+            .get("week_start")
+            .await?
+            .and_then(|value| value.as_str().map(WeekStart::from))
+            .unwrap_or(WeekStart::Sunday))
+    }
+
+    pub async fn load_title_overflow(&self) -> miette::Result<TitleOverflow> {
+        Ok(self
+            .get("title_overflow")
+            .await?
+            .and_then(|value| value.as_str().map(TitleOverflow::from))
+            .unwrap_or(TitleOverflow::Wrap))
+    }
+
+    /// Whether overdue todos move to today on startup (default true).
+    pub async fn load_auto_rollover(&self) -> miette::Result<bool> {
+        Ok(self
+            .get("auto_rollover")
+            .await?
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true))
+    }
+
+    /// Rollovers after which a todo is flagged as stale (default 3).
+    pub async fn load_stale_after(&self) -> miette::Result<u64> {
+        Ok(self
+            .get("stale_after")
+            .await?
+            .and_then(|value| value.as_u64())
+            .unwrap_or(DEFAULT_STALE_AFTER))
+    }
+
+    /// Zone that decides which day is "today" (default: the system zone).
+    pub async fn load_timezone(&self) -> miette::Result<TimeZonePref> {
+        Ok(self
+            .get("timezone")
+            .await?
+            .and_then(|value| value.as_str().and_then(|tz| tz.parse().ok()))
+            .unwrap_or_default())
+    }
+
+    pub async fn load_theme(&self) -> miette::Result<Theme> {
+        Ok(self
+            .get("theme")
+            .await?
+            .and_then(|value| value.as_str().map(Theme::from))
+            .unwrap_or(Theme::Default))
+    }
+
+    /// Per-role color overrides, keyed by role name (`focus`, `tag`, ...).
+    pub async fn load_color_overrides(&self) -> miette::Result<BTreeMap<String, String>> {
+        let entries = config::Entity::find()
+            .filter(config::Column::Key.starts_with(COLOR_KEY_PREFIX))
+            .all(&self.db)
             .await
             .into_diagnostic()?;
 
-        if let Some(model) = result
-            && let Some(value) = model.value.as_str()
-        {
-            return Ok(WeekStart::from(value));
-        }
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| {
+                let role = entry.key.strip_prefix(COLOR_KEY_PREFIX)?.to_string();
+
+                Some((role, entry.value.as_str()?.to_string()))
+            })
+            .collect())
+    }
+
+    /// The stored value of a registered setting, else its default.
+    pub async fn get(&self, key: &str) -> miette::Result<Option<JsonValue>> {
+        let def = settings::lookup(key)?;
+
+        Ok(self.load_value(key).await?.or_else(|| def.default_value()))
+    }
+
+    /// Check `input` against the setting's type and store it.
+    pub async fn set(&self, key: &str, input: &str) -> miette::Result<JsonValue> {
+        let value = settings::lookup(key)?.parse(input)?;
+
+        self.save_value(key, value.clone()).await?;
 
-        Ok(WeekStart::Sunday)
+        Ok(value)
     }
 
-    pub async fn save_week_start(&self, week_start: WeekStart) -> miette::Result<()> {
-        let now = Utc::now();
-        let model = config::ActiveModel {
-            key: Set("week_start".to_string()),
-            value: Set(json!(week_start.as_str())),
-            created_at: Set(now),
-            updated_at: Set(now),
-        };
+    /// Drop the stored value so the default applies again.
+    pub async fn reset(&self, key: &str) -> miette::Result<()> {
+        settings::lookup(key)?;
 
-        config::Entity::insert(model)
-            .on_conflict(
-                OnConflict::column(config::Column::Key)
-                    .update_columns([config::Column::Value, config::Column::UpdatedAt])
-                    .to_owned(),
-            )
+        config::Entity::delete_by_id(key.to_string())
             .exec(&self.db)
             .await
             .into_diagnostic()?;
 
         Ok(())
     }
+
+    /// Every registered setting with its current value.
+    pub async fn entries(&self) -> miette::Result<Vec<SettingEntry>> {
+        let mut stored: HashMap<String, JsonValue> = config::Entity::find()
+            .all(&self.db)
+            .await
+            .into_diagnostic()?
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect();
+
+        Ok(SETTINGS
+            .iter()
+            .map(|def| {
+                let value = stored.remove(def.key);
+
+                SettingEntry {
+                    def,
+                    is_set: value.is_some(),
+                    value: value.or_else(|| def.default_value()),
+                }
+            })
+            .collect())
+    }
+
+    async fn load_value(&self, key: &str) -> miette::Result<Option<JsonValue>> {
+        load_value(&self.db, key).await
+    }
+
+    async fn save_value(&self, key: &str, value: JsonValue) -> miette::Result<()> {
+        save_value(&self.db, &self.clock, key, value).await
+    }
+}
+
+/// The stored value for `key`, read through any connection so callers can
+/// use it inside a transaction.
+pub(crate) async fn load_value<C: ConnectionTrait>(
+    db: &C,
+    key: &str,
+) -> miette::Result<Option<JsonValue>> {
+    let result = config::Entity::find()
+        .filter(config::Column::Key.eq(key))
+        .one(db)
+        .await
+        .into_diagnostic()?;
+
+    Ok(result.map(|model| model.value))
+}
+
+/// Insert or replace the value for `key`.
+pub(crate) async fn save_value<C: ConnectionTrait>(
+    db: &C,
+    clock: &SharedClock,
+    key: &str,
+    value: JsonValue,
+) -> miette::Result<()> {
+    let now = clock.now();
+    let model = config::ActiveModel {
+        key: Set(key.to_string()),
+        value: Set(value),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    config::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(config::Column::Key)
+                .update_columns([config::Column::Value, config::Column::UpdatedAt])
+                .to_owned(),
+        )
+        .exec(db)
+        .await
+        .into_diagnostic()?;
+
+    Ok(())
 }
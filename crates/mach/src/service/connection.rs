@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use miette::{Context, IntoDiagnostic};
-use sea_orm::{Database, DatabaseConnection};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 use tokio::fs;
 use tokio::fs::OpenOptions;
 
@@ -32,15 +32,36 @@ pub async fn init_database(path: impl AsRef<Path>) -> miette::Result<DatabaseCon
         .into_diagnostic()
         .wrap_err("failed to open SeaORM SQLite connection")?;
 
-    conn.get_schema_registry("machich::entity::*")
-        .sync(&conn)
+    sync_schema(&conn).await?;
+
+    Ok(conn)
+}
+
+/// Open a private in-memory SQLite database with the schema applied.
+pub async fn init_memory_database() -> miette::Result<DatabaseConnection> {
+    let mut options = ConnectOptions::new("sqlite::memory:");
+
+    // Every pooled connection would otherwise get its own empty database.
+    options.max_connections(1).min_connections(1);
+
+    let conn = Database::connect(options)
         .await
         .into_diagnostic()
-        .wrap_err("failed to synchronize schema via SeaORM entity registry")?;
+        .wrap_err("failed to open in-memory SQLite connection")?;
+
+    sync_schema(&conn).await?;
 
     Ok(conn)
 }
 
+async fn sync_schema(conn: &DatabaseConnection) -> miette::Result<()> {
+    conn.get_schema_registry("machich::entity::*")
+        .sync(conn)
+        .await
+        .into_diagnostic()
+        .wrap_err("failed to synchronize schema via SeaORM entity registry")
+}
+
 fn sqlite_url(path: &str) -> String {
     format!("sqlite://{path}?mode=rwc")
 }
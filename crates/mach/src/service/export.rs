@@ -0,0 +1,128 @@
+//! iCalendar (RFC 5545) export of scheduled todos, used by `:export ics`.
+//!
+//! Each todo becomes an all-day event on its day, so any calendar app can
+//! show the plan. Done todos keep their day with a `✓` in front.
+
+use chrono::{DateTime, Duration, Utc};
+use miette::Result;
+
+use crate::entity::todo;
+
+use super::todo::{PlacementQuery, TodoQuery, TodoService};
+
+/// Longest content line before it is folded, in octets.
+const LINE_LIMIT: usize = 75;
+
+/// An iCalendar file and how many events it holds.
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub text: String,
+    pub events: usize,
+}
+
+impl TodoService {
+    /// Every scheduled todo, pending or done, as a calendar in day order.
+    pub async fn export_ics(&self) -> Result<Calendar> {
+        let query = TodoQuery::new().placement(PlacementQuery::Scheduled);
+        let todos = self.query(&query).await?;
+
+        Ok(Calendar {
+            text: to_ics(&todos, self.clock.now()),
+            events: todos.len(),
+        })
+    }
+}
+
+/// Render `todos` that have a day as all-day events, stamped `now`.
+pub fn to_ics(todos: &[todo::Model], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//machich//mach//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for todo in todos {
+        let Some(day) = todo.scheduled_for else {
+            continue;
+        };
+
+        let summary = if todo.status == "done" {
+            format!("✓ {}", todo.title)
+        } else {
+            todo.title.clone()
+        };
+
+        lines.push("BEGIN:VEVENT".into());
+        lines.push(format!("UID:{}@machich", todo.id));
+        lines.push(format!("DTSTAMP:{}", timestamp(now)));
+        lines.push(format!("LAST-MODIFIED:{}", timestamp(todo.updated_at)));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", day.format("%Y%m%d")));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (day + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", escape(&summary)));
+
+        if let Some(notes) = todo.notes.as_deref().filter(|notes| !notes.is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape(notes)));
+        }
+
+        let tags = todo.tags();
+
+        if !tags.is_empty() {
+            let tags: Vec<String> = tags.iter().map(|tag| escape(tag)).collect();
+
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+
+        lines.push("END:VEVENT".into());
+    }
+
+    lines.push("END:VCALENDAR".into());
+
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value: backslashes, separators and newlines.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// Split a content line into 75-octet pieces, continuing each with a space,
+/// without breaking a character apart.
+fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut width = 0;
+
+    for ch in line.chars() {
+        if width + ch.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+
+            // The leading space counts towards the next line.
+            width = 1;
+        }
+
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+
+    out
+}
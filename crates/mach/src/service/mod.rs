@@ -1,15 +1,27 @@
+pub mod agenda;
+pub mod backlog;
+pub mod clock;
 pub mod config;
 pub mod connection;
+pub mod export;
+pub mod query;
+pub mod settings;
 pub mod todo;
+pub mod watch;
 
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
-use chrono::{Local, NaiveDate};
+use chrono::NaiveDate;
 use directories::ProjectDirs;
 use miette::{Context, IntoDiagnostic};
+use sea_orm::DatabaseConnection;
+
+use crate::entity::todo as todo_entity;
 
 use self::{
-    config::{ConfigService, WeekStart},
+    backlog::BacklogService,
+    clock::{FixedClock, SharedClock, SystemClock, TimeZonePref},
+    config::{ConfigService, DEFAULT_STALE_AFTER, Theme, TitleOverflow, WeekStart},
     connection::init_database,
     todo::TodoService,
 };
@@ -18,39 +30,146 @@ use self::{
 pub struct Services {
     pub todos: TodoService,
     pub config: ConfigService,
+    pub backlog: BacklogService,
+    clock: SharedClock,
+    /// Started with `--today`: the day is fixed and rollover is skipped.
+    pinned: bool,
+    timezone: TimeZonePref,
     today: NaiveDate,
     week_start_pref: WeekStart,
+    title_overflow_pref: TitleOverflow,
+    stale_after_pref: u64,
+    theme_pref: Theme,
+    color_overrides: BTreeMap<String, String>,
+    auto_rollover: bool,
 }
 
 impl Services {
-    pub async fn bootstrap() -> miette::Result<Self> {
+    /// Open the default database on the wall clock, or with the clock
+    /// pinned to noon on `today` in the configured time zone (`--today`).
+    ///
+    /// A pinned day is for looking around: rollover doesn't run, so todos
+    /// aren't moved onto a date that isn't really today.
+    pub async fn bootstrap(today: Option<NaiveDate>) -> miette::Result<Self> {
         let db_path = default_db_path()?;
 
         let conn = init_database(&db_path).await?;
 
-        let todos = TodoService::new(conn.clone());
-        let config = ConfigService::new(conn.clone());
+        let Some(today) = today else {
+            return Self::open(conn, Arc::new(SystemClock)).await;
+        };
+
+        let zone = ConfigService::new(conn.clone(), Arc::new(SystemClock))
+            .load_timezone()
+            .await?;
 
-        let today = Local::now().date_naive();
+        Self::open_with(conn, Arc::new(FixedClock::on(today, zone)), true).await
+    }
 
-        todos.rollover_to(today).await?;
-        let week_start = config.load_week_start().await?;
+    /// Build services over an initialized connection, load preferences and
+    /// run the startup rollover.
+    pub async fn open(conn: DatabaseConnection, clock: SharedClock) -> miette::Result<Self> {
+        Self::open_with(conn, clock, false).await
+    }
 
-        Ok(Self {
+    async fn open_with(
+        conn: DatabaseConnection,
+        clock: SharedClock,
+        pinned: bool,
+    ) -> miette::Result<Self> {
+        let todos = TodoService::new(conn.clone(), clock.clone());
+        let config = ConfigService::new(conn, clock.clone());
+        let backlog = BacklogService::new(todos.clone(), clock.clone());
+
+        let mut services = Self {
             todos,
             config,
-            today,
-            week_start_pref: week_start,
-        })
+            backlog,
+            clock,
+            pinned,
+            timezone: TimeZonePref::default(),
+            today: NaiveDate::default(),
+            week_start_pref: WeekStart::Sunday,
+            title_overflow_pref: TitleOverflow::Wrap,
+            stale_after_pref: DEFAULT_STALE_AFTER,
+            theme_pref: Theme::Default,
+            color_overrides: BTreeMap::new(),
+            auto_rollover: true,
+        };
+
+        services.reload_preferences().await?;
+        services.start_day(services.current_date()).await?;
+
+        Ok(services)
+    }
+
+    /// Re-read every preference from config, e.g. after a settings change.
+    /// The day being worked on is left alone.
+    pub async fn reload_preferences(&mut self) -> miette::Result<()> {
+        self.timezone = self.config.load_timezone().await?;
+        self.auto_rollover = self.config.load_auto_rollover().await?;
+        self.week_start_pref = self.config.load_week_start().await?;
+        self.title_overflow_pref = self.config.load_title_overflow().await?;
+        self.stale_after_pref = self.config.load_stale_after().await?;
+        self.theme_pref = self.config.load_theme().await?;
+        self.color_overrides = self.config.load_color_overrides().await?;
+
+        Ok(())
     }
 
+    /// Treat `today` as the current day, rolling overdue todos onto it when
+    /// `auto_rollover` is enabled and the day isn't pinned by `--today`.
+    pub async fn start_day(&mut self, today: NaiveDate) -> miette::Result<()> {
+        self.today = today;
+
+        if self.auto_rollover && !self.pinned {
+            self.todos.rollover_to(today).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The day the app is working on; advanced by [`Services::start_day`].
     pub fn today(&self) -> NaiveDate {
         self.today
     }
 
+    /// The calendar date right now according to the clock and time zone.
+    pub fn current_date(&self) -> NaiveDate {
+        self.timezone.date_of(self.clock.now())
+    }
+
+    pub fn timezone(&self) -> TimeZonePref {
+        self.timezone
+    }
+
     pub fn week_start(&self) -> WeekStart {
         self.week_start_pref
     }
+
+    pub fn title_overflow(&self) -> TitleOverflow {
+        self.title_overflow_pref
+    }
+
+    /// Rollovers after which a todo is shown as stale.
+    pub fn stale_after(&self) -> u64 {
+        self.stale_after_pref
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme_pref
+    }
+
+    /// `color.<role>` overrides from config, keyed by role.
+    pub fn color_overrides(&self) -> &BTreeMap<String, String> {
+        &self.color_overrides
+    }
+
+    /// Pending todos that rollover carried onto today, as recorded in
+    /// their metadata.
+    pub async fn rolled_over(&self) -> miette::Result<Vec<todo_entity::Model>> {
+        self.todos.rolled_over_on(self.today).await
+    }
 }
 
 fn default_db_path() -> miette::Result<PathBuf> {
@@ -0,0 +1,135 @@
+//! Textual query language for selecting todos.
+//!
+//! A query is a list of whitespace-separated terms, all of which must match:
+//!
+//! - `status:pending|done|any`, `is:pending`, `is:done`, `is:overdue`
+//! - `tag:work` or `#work`
+//! - `due:fri`, `due<fri`, `due<=2026-11-02`, `due>today`, `due>=-7d`
+//! - `in:backlog`, `in:week`, `col:2` (backlog column, counted from 1)
+//! - `title:/regex/` or `title:word`
+//! - `sort:day|title|created|status`, `limit:20`
+//! - anything else is searched for in titles and notes.
+
+use chrono::{Duration, NaiveDate};
+use miette::{Result, bail};
+
+use crate::dates::{parse_date, start_of_week};
+
+use super::config::WeekStart;
+use super::todo::{PlacementQuery, SortKey, StatusQuery, TodoQuery};
+
+impl TodoQuery {
+    /// Parse the query language described in this module.
+    pub fn parse(input: &str, today: NaiveDate, week_start: WeekStart) -> Result<Self> {
+        TodoQuery::new().with_terms(input, today, week_start)
+    }
+
+    /// Apply the terms in `input` on top of this query, so they override
+    /// defaults such as the status.
+    pub fn with_terms(self, input: &str, today: NaiveDate, week_start: WeekStart) -> Result<Self> {
+        let mut query = self;
+        let mut words = Vec::new();
+
+        for term in input.split_whitespace() {
+            if let Some(tag) = term.strip_prefix('#') {
+                query = query.tag(tag);
+
+                continue;
+            }
+
+            if let Some((op, value)) = split_comparison(term, "due") {
+                let date = parse_term_date(value, today)?;
+
+                query = match op {
+                    ":" | "=" => query.from(date).to(date),
+                    "<" => query.to(date - Duration::days(1)),
+                    "<=" => query.to(date),
+                    ">" => query.from(date + Duration::days(1)),
+                    ">=" => query.from(date),
+                    _ => unreachable!(),
+                };
+
+                continue;
+            }
+
+            let Some((key, value)) = term.split_once(':') else {
+                words.push(term);
+
+                continue;
+            };
+
+            query = match (key.to_ascii_lowercase().as_str(), value) {
+                ("status" | "is", "pending") => query.status(StatusQuery::Pending),
+                ("status" | "is", "done") => query.status(StatusQuery::Done),
+                ("status", "any") => query.status(StatusQuery::Any),
+                ("is", "overdue") => query
+                    .status(StatusQuery::Pending)
+                    .to(today - Duration::days(1)),
+                ("tag", tag) => query.tag(tag),
+                ("in", "backlog") => query.placement(PlacementQuery::Backlog(None)),
+                ("in", "week") => {
+                    let start = start_of_week(today, week_start);
+
+                    query.from(start).to(start + Duration::days(6))
+                }
+                ("col" | "column", col) => {
+                    let Some(number) = col.parse::<i64>().ok().filter(|number| *number >= 1) else {
+                        bail!("invalid backlog column '{col}'");
+                    };
+
+                    query.placement(PlacementQuery::Backlog(Some(number - 1)))
+                }
+                ("title", pattern) => {
+                    let pattern = pattern
+                        .strip_prefix('/')
+                        .and_then(|p| p.strip_suffix('/'))
+                        .map(str::to_string)
+                        .unwrap_or_else(|| regex::escape(pattern));
+
+                    query.title_matches(&pattern)?
+                }
+                ("sort", key) => query.sort(parse_sort(key)?),
+                ("limit", n) => {
+                    let Ok(n) = n.parse::<usize>() else {
+                        bail!("invalid limit '{n}'");
+                    };
+
+                    query.limit(n)
+                }
+                _ => bail!("unknown query term '{term}'"),
+            };
+        }
+
+        if !words.is_empty() {
+            query = query.search(&words.join(" "));
+        }
+
+        Ok(query)
+    }
+}
+
+pub fn parse_sort(key: &str) -> Result<SortKey> {
+    Ok(match key {
+        "day" | "due" | "date" => SortKey::Day,
+        "title" => SortKey::Title,
+        "created" => SortKey::Created,
+        "status" => SortKey::Status,
+        _ => bail!("unknown sort key '{key}'"),
+    })
+}
+
+/// Split `due<=fri` into (`<=`, `fri`) when `term` starts with `field`.
+fn split_comparison<'a>(term: &'a str, field: &str) -> Option<(&'a str, &'a str)> {
+    let rest = term.strip_prefix(field)?;
+
+    ["<=", ">=", "<", ">", ":", "="]
+        .into_iter()
+        .find_map(|op| rest.strip_prefix(op).map(|value| (op, value)))
+}
+
+fn parse_term_date(value: &str, today: NaiveDate) -> Result<NaiveDate> {
+    match parse_date(value, today) {
+        Some(date) => Ok(date),
+        None => bail!("unrecognized date '{value}'"),
+    }
+}
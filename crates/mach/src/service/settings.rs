@@ -0,0 +1,245 @@
+//! Registry of user preferences stored in `config_entries`: each key's type,
+//! default and description, and how typed input is checked and stored.
+
+use std::str::FromStr;
+
+use serde_json::{Value as JsonValue, json};
+
+use super::clock::TimeZonePref;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    /// One of a fixed set of words.
+    Choice(&'static [&'static str]),
+    Bool,
+    /// A whole number in `min..=max`.
+    Count {
+        min: u64,
+        max: u64,
+    },
+    /// `local` or an IANA zone name.
+    TimeZone,
+    /// A color name, `#rrggbb` or a 0–255 index.
+    Color,
+}
+
+#[derive(Debug)]
+pub struct SettingDef {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    /// Value when unset, as it would be typed. `None` leaves it to the code
+    /// reading it (e.g. colors follow the theme).
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+}
+
+const fn color(key: &'static str, description: &'static str) -> SettingDef {
+    SettingDef {
+        key,
+        kind: SettingKind::Color,
+        default: None,
+        description,
+    }
+}
+
+/// Every setting, in the order they're listed.
+pub static SETTINGS: &[SettingDef] = &[
+    SettingDef {
+        key: "week_start",
+        kind: SettingKind::Choice(&["sunday", "monday"]),
+        default: Some("sunday"),
+        description: "First day of the week on the board",
+    },
+    SettingDef {
+        key: "title_overflow",
+        kind: SettingKind::Choice(&["wrap", "truncate"]),
+        default: Some("wrap"),
+        description: "Wrap long titles or cut them with …",
+    },
+    SettingDef {
+        key: "auto_rollover",
+        kind: SettingKind::Bool,
+        default: Some("true"),
+        description: "Move overdue todos to today on startup",
+    },
+    SettingDef {
+        key: "stale_after",
+        kind: SettingKind::Count { min: 0, max: 999 },
+        default: Some("3"),
+        description: "Rollovers before a todo is marked stale",
+    },
+    SettingDef {
+        key: "timezone",
+        kind: SettingKind::TimeZone,
+        default: Some("local"),
+        description: "Zone that decides which day is today",
+    },
+    SettingDef {
+        key: "theme",
+        kind: SettingKind::Choice(&["default", "high-contrast", "solarized", "monochrome"]),
+        default: Some("default"),
+        description: "Color theme (NO_COLOR forces monochrome)",
+    },
+    color("color.text", "Todo titles"),
+    color("color.text_dim", "Done todos and hints"),
+    color("color.focus", "Focused column"),
+    color("color.active", "Focused todo"),
+    color("color.accent", "Selected and marked todos"),
+    color("color.stale", "Todos that keep rolling over"),
+    color("color.error", "Errors in the status bar"),
+    color("color.border", "Separators"),
+    color("color.tag", "#tag labels"),
+];
+
+/// The definition for `key`.
+pub fn lookup(key: &str) -> miette::Result<&'static SettingDef> {
+    SETTINGS.iter().find(|def| def.key == key).ok_or_else(|| {
+        let keys: Vec<&str> = SETTINGS.iter().map(|def| def.key).collect();
+
+        miette::miette!(
+            help = format!("known settings: {}", keys.join(", ")),
+            "unknown setting '{key}'"
+        )
+    })
+}
+
+impl SettingDef {
+    /// Check typed input and convert it to the stored JSON value.
+    pub fn parse(&self, input: &str) -> miette::Result<JsonValue> {
+        let input = input.trim();
+        let key = self.key;
+
+        let value = match self.kind {
+            SettingKind::Choice(options) => {
+                let lower = input.to_ascii_lowercase();
+
+                if !options.contains(&lower.as_str()) {
+                    miette::bail!("{key} must be one of {}, not '{input}'", options.join(", "));
+                }
+
+                json!(lower)
+            }
+            SettingKind::Bool => match input.to_ascii_lowercase().as_str() {
+                "true" | "on" | "yes" => json!(true),
+                "false" | "off" | "no" => json!(false),
+                _ => miette::bail!("{key} must be true or false, not '{input}'"),
+            },
+            SettingKind::Count { min, max } => match input.parse::<u64>() {
+                Ok(count) if (min..=max).contains(&count) => json!(count),
+                _ => miette::bail!("{key} must be a number from {min} to {max}, not '{input}'"),
+            },
+            SettingKind::TimeZone => {
+                let timezone = TimeZonePref::from_str(input).map_err(|err| miette::miette!(err))?;
+
+                json!(timezone.as_str())
+            }
+            SettingKind::Color => {
+                if !is_color(input) {
+                    miette::bail!("{key} must be a color name, #rrggbb or 0-255, not '{input}'");
+                }
+
+                json!(input.to_ascii_lowercase())
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// The value used when the key isn't stored.
+    pub fn default_value(&self) -> Option<JsonValue> {
+        self.default.and_then(|default| self.parse(default).ok())
+    }
+
+    /// A stored value as it would be typed.
+    pub fn format(&self, value: &JsonValue) -> String {
+        match value {
+            JsonValue::String(text) => text.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    /// The values to step through for settings picked rather than typed.
+    pub fn choices(&self) -> Option<&'static [&'static str]> {
+        match self.kind {
+            SettingKind::Choice(options) => Some(options),
+            SettingKind::Bool => Some(&["true", "false"]),
+            _ => None,
+        }
+    }
+}
+
+/// A setting and its current value.
+#[derive(Debug, Clone)]
+pub struct SettingEntry {
+    pub def: &'static SettingDef,
+    /// Stored value, else the default.
+    pub value: Option<JsonValue>,
+    /// Whether a value is stored rather than defaulted.
+    pub is_set: bool,
+}
+
+impl SettingEntry {
+    /// The choice `offset` steps from the current one, wrapping around; for
+    /// settings picked from a list.
+    pub fn step(&self, offset: isize) -> &'static str {
+        let choices = self.def.choices().unwrap_or_default();
+        let current = self.value.as_ref().map(|value| self.def.format(value));
+        let idx = choices
+            .iter()
+            .position(|choice| Some(*choice) == current.as_deref())
+            .unwrap_or(0);
+
+        let len = choices.len() as isize;
+
+        choices[(idx as isize + offset).rem_euclid(len) as usize]
+    }
+
+    /// The value as typed, or `(theme)` / `(unset)` when there is none.
+    pub fn display(&self) -> String {
+        match &self.value {
+            Some(value) => self.def.format(value),
+            None if self.def.kind == SettingKind::Color => "(theme)".to_string(),
+            None => "(unset)".to_string(),
+        }
+    }
+}
+
+/// Color names as the TUI spells them, after folding case and separators.
+const COLOR_NAMES: &[&str] = &[
+    "reset",
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "gray",
+    "darkgray",
+    "lightred",
+    "lightgreen",
+    "lightyellow",
+    "lightblue",
+    "lightmagenta",
+    "lightcyan",
+    "white",
+];
+
+/// Whether `input` is a color name (`lightred`, `light-red`, `grey`), a
+/// `#rrggbb` hex triplet or a 0–255 palette index.
+fn is_color(input: &str) -> bool {
+    if input.parse::<u8>().is_ok() {
+        return true;
+    }
+
+    if let Some(hex) = input.strip_prefix('#') {
+        return hex.len() == 6 && hex.chars().all(|ch| ch.is_ascii_hexdigit());
+    }
+
+    let name = input
+        .to_ascii_lowercase()
+        .replace([' ', '-', '_'], "")
+        .replace("grey", "gray");
+
+    COLOR_NAMES.contains(&name.as_str())
+}
@@ -1,15 +1,27 @@
 use crate::entity::todo;
 use chrono::NaiveDate;
 use miette::{IntoDiagnostic, Result, bail};
+use regex::Regex;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, QueryFilter,
-    QueryOrder, Set, sea_query::Expr,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, EntityTrait, Order, QueryFilter, QueryOrder, Set, TransactionTrait,
+    sea_query::Expr,
 };
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
+use super::clock::SharedClock;
+
 const STATUS_DONE: &str = "done";
 
+/// Spacing between neighbouring `order_index` values, leaving room to slot
+/// a todo in between without renumbering its column.
+const ORDER_GAP: i64 = 1 << 10;
+
+/// Index of the first todo in a column (and where a respaced column
+/// starts), leaving room for many todos to be added on top of it.
+const ORDER_BASE: i64 = 1 << 32;
+
 /// Scope to fetch/move todos.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ListScope {
@@ -33,6 +45,203 @@ impl ListOptions {
     }
 }
 
+/// Status filter for [`TodoQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusQuery {
+    Pending,
+    Done,
+    #[default]
+    Any,
+}
+
+/// Where a todo lives, for [`TodoQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlacementQuery {
+    /// Only todos scheduled for a day.
+    Scheduled,
+    /// Only backlog todos, optionally restricted to one backlog column.
+    Backlog(Option<i64>),
+    #[default]
+    Anywhere,
+}
+
+/// Ordering for [`TodoQuery`] results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    /// By day (backlog last), then column order.
+    #[default]
+    Day,
+    Title,
+    Created,
+    /// Pending before done, then by day.
+    Status,
+}
+
+/// Filter for selecting todos across days, the backlog, tags and titles.
+///
+/// Date, status, placement and text filters run in SQL; tag and title regex
+/// filters are applied to the fetched rows since they live in JSON/free text.
+/// See [`TodoQuery::parse`] for the textual form.
+#[derive(Debug, Clone, Default)]
+pub struct TodoQuery {
+    pub status: StatusQuery,
+    pub placement: PlacementQuery,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    pub title: Option<Regex>,
+    pub text: Option<String>,
+    pub sort: SortKey,
+    pub limit: Option<usize>,
+}
+
+impl TodoQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: StatusQuery) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn placement(mut self, placement: PlacementQuery) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Restrict to todos scheduled on or after `date` (implies scheduled).
+    pub fn from(mut self, date: NaiveDate) -> Self {
+        self.from = Some(date);
+        self
+    }
+
+    /// Restrict to todos scheduled on or before `date` (implies scheduled).
+    pub fn to(mut self, date: NaiveDate) -> Self {
+        self.to = Some(date);
+        self
+    }
+
+    /// Require a tag; repeat to require several.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(normalize_tag(tag));
+        self
+    }
+
+    /// Require titles matching `pattern` (case-insensitive regex).
+    pub fn title_matches(mut self, pattern: &str) -> Result<Self> {
+        let regex = regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .into_diagnostic()?;
+
+        self.title = Some(regex);
+
+        Ok(self)
+    }
+
+    /// Require `text` in the title or notes (case-insensitive substring).
+    pub fn search(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn sort(mut self, sort: SortKey) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Whether any criterion narrows the selection.
+    pub fn is_unfiltered(&self) -> bool {
+        self.status == StatusQuery::Any
+            && self.placement == PlacementQuery::Anywhere
+            && self.from.is_none()
+            && self.to.is_none()
+            && self.tags.is_empty()
+            && self.title.is_none()
+            && self.text.is_none()
+    }
+
+    /// The SQL-expressible part of the query.
+    pub fn condition(&self) -> Condition {
+        let mut cond = Condition::all();
+
+        cond = match self.status {
+            StatusQuery::Pending => cond.add(todo::Column::Status.ne(STATUS_DONE)),
+            StatusQuery::Done => cond.add(todo::Column::Status.eq(STATUS_DONE)),
+            StatusQuery::Any => cond,
+        };
+
+        cond = match self.placement {
+            PlacementQuery::Scheduled => cond.add(todo::Column::ScheduledFor.is_not_null()),
+            PlacementQuery::Backlog(column) => {
+                let cond = cond.add(todo::Column::ScheduledFor.is_null());
+
+                match column {
+                    Some(column) => cond.add(todo::Column::BacklogColumn.eq(column)),
+                    None => cond,
+                }
+            }
+            PlacementQuery::Anywhere => cond,
+        };
+
+        if let Some(from) = self.from {
+            cond = cond.add(todo::Column::ScheduledFor.gte(from));
+        }
+
+        if let Some(to) = self.to {
+            cond = cond.add(todo::Column::ScheduledFor.lte(to));
+        }
+
+        if let Some(text) = &self.text {
+            cond = cond.add(
+                Condition::any()
+                    .add(todo::Column::Title.contains(text))
+                    .add(todo::Column::Notes.contains(text)),
+            );
+        }
+
+        cond
+    }
+
+    /// Whether a fetched row passes the non-SQL filters.
+    pub fn matches(&self, model: &todo::Model) -> bool {
+        if let Some(regex) = &self.title
+            && !regex.is_match(&model.title)
+        {
+            return false;
+        }
+
+        if self.tags.is_empty() {
+            return true;
+        }
+
+        let tags = model.tags();
+
+        self.tags.iter().all(|tag| tags.contains(tag))
+    }
+}
+
+impl From<ListOptions> for TodoQuery {
+    fn from(opts: ListOptions) -> Self {
+        let query = TodoQuery::new().status(if opts.include_done {
+            StatusQuery::Any
+        } else {
+            StatusQuery::Pending
+        });
+
+        match opts.scope {
+            ListScope::Day(date) => query.from(date).to(date),
+            ListScope::Backlog => query.placement(PlacementQuery::Backlog(None)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MovePlacement {
     Top,
@@ -48,17 +257,47 @@ pub enum ReorderDirection {
 #[derive(Clone)]
 pub struct TodoService {
     db: DatabaseConnection,
+    pub(super) clock: SharedClock,
 }
 
 impl TodoService {
-    pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+    pub fn new(db: DatabaseConnection, clock: SharedClock) -> Self {
+        Self { db, clock }
     }
 
     pub fn connection(&self) -> &DatabaseConnection {
         &self.db
     }
 
+    /// Run `f` in one write transaction, committing if it succeeds and
+    /// rolling back if it fails. Use it to batch several mutations so they
+    /// apply together.
+    pub async fn transaction<T>(
+        &self,
+        f: impl AsyncFnOnce(&TodoTransaction) -> Result<T>,
+    ) -> Result<T> {
+        let txn = self.db.begin().await.into_diagnostic()?;
+
+        // SQLite starts transactions deferred; a reader that later writes
+        // fails with SQLITE_BUSY if another connection wrote meanwhile.
+        // Taking the write lock up front makes concurrent writers wait
+        // their turn instead.
+        txn.execute_unprepared("UPDATE todos SET id = id WHERE 0")
+            .await
+            .into_diagnostic()?;
+
+        let txn = TodoTransaction {
+            txn,
+            clock: self.clock.clone(),
+        };
+
+        let value = f(&txn).await?;
+
+        txn.txn.commit().await.into_diagnostic()?;
+
+        Ok(value)
+    }
+
     /// Insert a todo either scheduled for a date or backlog.
     pub async fn add(
         &self,
@@ -66,20 +305,50 @@ impl TodoService {
         scheduled_for: Option<NaiveDate>,
         notes: Option<String>,
     ) -> Result<todo::Model> {
-        let order_index = self.next_top_order_index(scheduled_for).await?;
+        self.transaction(async |txn| txn.add(title, scheduled_for, 0, notes, Slot::Top).await)
+            .await
+    }
 
-        let model = todo::ActiveModel {
-            id: Set(Uuid::new_v4()),
-            title: Set(title.into()),
-            status: Set("pending".to_string()),
-            scheduled_for: Set(scheduled_for),
-            order_index: Set(order_index),
-            notes: Set(notes),
-            metadata: Set(JsonValue::Null),
-            ..Default::default()
-        };
+    /// Insert several todos in one transaction, keeping `titles` in order
+    /// at the top or bottom of their day or backlog column. `backlog_column`
+    /// only applies to backlog todos.
+    pub async fn add_many(
+        &self,
+        titles: &[String],
+        scheduled_for: Option<NaiveDate>,
+        backlog_column: Option<i64>,
+        notes: Option<String>,
+        placement: MovePlacement,
+    ) -> Result<Vec<todo::Model>> {
+        let backlog_column = backlog_column
+            .filter(|_| scheduled_for.is_none())
+            .unwrap_or(0);
+
+        self.transaction(async |txn| {
+            let mut added = Vec::with_capacity(titles.len());
+
+            for (position, title) in titles.iter().enumerate() {
+                let slot = match placement {
+                    MovePlacement::Top => Slot::At(position),
+                    MovePlacement::Bottom => Slot::Bottom,
+                };
+
+                let model = txn
+                    .add(
+                        title.as_str(),
+                        scheduled_for,
+                        backlog_column,
+                        notes.clone(),
+                        slot,
+                    )
+                    .await?;
+
+                added.push(model);
+            }
 
-        model.insert(&self.db).await.into_diagnostic()
+            Ok(added)
+        })
+        .await
     }
 
     /// List todos using the provided filters.
@@ -100,27 +369,316 @@ impl TodoService {
             .into_diagnostic()
     }
 
+    /// Fetch todos matching `query`, in the query's sort order. The default
+    /// orders by day (backlog last), then as they appear within their column.
+    pub async fn query(&self, query: &TodoQuery) -> Result<Vec<todo::Model>> {
+        let backlog_last = Expr::cust("CASE WHEN scheduled_for IS NULL THEN 1 ELSE 0 END");
+        let done_first = Expr::cust("CASE WHEN status = 'done' THEN 1 ELSE 0 END");
+
+        let mut select = todo::Entity::find().filter(query.condition());
+
+        select = match query.sort {
+            SortKey::Day => select,
+            SortKey::Title => select.order_by_asc(Expr::cust("LOWER(title)")),
+            SortKey::Created => select.order_by_asc(todo::Column::CreatedAt),
+            SortKey::Status => select.order_by(done_first.clone(), Order::Asc),
+        };
+
+        let rows = select
+            .order_by(backlog_last, Order::Asc)
+            .order_by_asc(todo::Column::ScheduledFor)
+            .order_by_asc(todo::Column::BacklogColumn)
+            .order_by(done_first, Order::Asc)
+            .order_by_asc(todo::Column::OrderIndex)
+            .all(&self.db)
+            .await
+            .into_diagnostic()?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|row| query.matches(row))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
     /// Delete a todo by id.
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        self.transaction(async |txn| txn.delete(id).await).await
+    }
+
+    /// Mark a todo as complete, ensuring backlog items move into today's column.
+    pub async fn mark_done(&self, id: Uuid, today: NaiveDate) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.mark_done(id, today).await)
+            .await
+    }
+
+    /// Revert a completed todo back to a pending state.
+    pub async fn mark_pending(&self, id: Uuid) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.mark_pending(id).await)
+            .await
+    }
+
+    /// Move overdue todos (scheduled in the past) to today, counting each
+    /// rollover in the todo's metadata. Returns the ids that moved.
+    pub async fn rollover_to(&self, today: NaiveDate) -> Result<Vec<Uuid>> {
+        self.transaction(async |txn| txn.rollover_to(today).await)
+            .await
+    }
+
+    /// Pending todos on `date` that rollover carried there, in board order.
+    pub async fn rolled_over_on(&self, date: NaiveDate) -> Result<Vec<todo::Model>> {
+        let rows = self.list(ListOptions::today(date)).await?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|model| model.rolled_on() == Some(date))
+            .collect())
+    }
+
+    /// Move a todo to another column (day/backlog) placing it at the top or bottom.
+    pub async fn move_to_scope(
+        &self,
+        id: Uuid,
+        scope: ListScope,
+        placement: MovePlacement,
+    ) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.move_to_scope(id, scope, placement).await)
+            .await
+    }
+
+    /// Move a todo into `scope` at `position` (0-based) among the todos of
+    /// the same status there, clamped to the end of the column.
+    pub async fn move_to_position(
+        &self,
+        id: Uuid,
+        scope: ListScope,
+        position: usize,
+    ) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.move_to_position(id, scope, position).await)
+            .await
+    }
+
+    /// Mark several todos as complete in a single transaction.
+    pub async fn complete_many(&self, ids: &[Uuid], today: NaiveDate) -> Result<usize> {
+        self.transaction(async |txn| {
+            for &id in ids {
+                txn.mark_done(id, today).await?;
+            }
+
+            Ok(ids.len())
+        })
+        .await
+    }
+
+    /// Delete several todos in a single statement, returning how many were removed.
+    pub async fn delete_many(&self, ids: &[Uuid]) -> Result<u64> {
+        self.transaction(async |txn| txn.delete_many(ids).await)
+            .await
+    }
+
+    /// Move several todos into one column in a single transaction, keeping
+    /// their relative order (`ids` is expected top to bottom).
+    pub async fn move_many_to_scope(
+        &self,
+        ids: &[Uuid],
+        scope: ListScope,
+        placement: MovePlacement,
+    ) -> Result<usize> {
+        // Each move lands at the top, so insert bottom-most first.
+        let ordered: Vec<Uuid> = match placement {
+            MovePlacement::Top => ids.iter().rev().copied().collect(),
+            MovePlacement::Bottom => ids.to_vec(),
+        };
+
+        self.transaction(async |txn| {
+            for id in ordered {
+                txn.move_to_scope(id, scope, placement).await?;
+            }
+
+            Ok(ids.len())
+        })
+        .await
+    }
+
+    /// Move a todo to `slot` in backlog column `column`.
+    pub async fn move_to_backlog(&self, id: Uuid, column: i64, slot: Slot) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.move_to_backlog(id, Some(column), slot).await)
+            .await
+    }
+
+    /// Send several todos to the bottom of a backlog column in a single
+    /// transaction, keeping their relative order. `None` keeps each todo's
+    /// own column.
+    pub async fn move_many_to_backlog(&self, ids: &[Uuid], column: Option<i64>) -> Result<usize> {
+        self.transaction(async |txn| {
+            for &id in ids {
+                txn.move_to_backlog(id, column, Slot::Bottom).await?;
+            }
+
+            Ok(ids.len())
+        })
+        .await
+    }
+
+    /// Apply tag edits such as `+home -work` to several todos in a single
+    /// transaction. A bare word adds the tag, like `+word`.
+    pub async fn retag_many<S: AsRef<str>>(&self, ids: &[Uuid], tokens: &[S]) -> Result<usize> {
+        let edits = tag_edits(tokens)?;
+
+        self.transaction(async |txn| {
+            for &id in ids {
+                for (tag, add) in &edits {
+                    txn.set_tag(id, tag, *add).await?;
+                }
+            }
+
+            Ok(ids.len())
+        })
+        .await
+    }
+
+    /// Get a todo by id.
+    pub async fn get(&self, id: Uuid) -> Result<todo::Model> {
+        load(&self.db, id).await
+    }
+
+    /// Resolve a full id or a unique id prefix (as printed by `mach list`).
+    pub async fn resolve_id(&self, input: &str) -> Result<Uuid> {
+        if let Ok(id) = Uuid::parse_str(input) {
+            return Ok(id);
+        }
+
+        let prefix = input.trim().to_ascii_lowercase();
+
+        if prefix.is_empty() {
+            bail!("todo id must not be empty");
+        }
+
+        let matches: Vec<Uuid> = todo::Entity::find()
+            .all(&self.db)
+            .await
+            .into_diagnostic()?
+            .into_iter()
+            .map(|model| model.id)
+            .filter(|id| id.to_string().starts_with(&prefix))
+            .collect();
+
+        match matches.as_slice() {
+            [id] => Ok(*id),
+            [] => bail!("no todo matches id '{input}'"),
+            _ => bail!("id '{input}' is ambiguous ({} matches)", matches.len()),
+        }
+    }
+
+    /// Update the title of a todo.
+    pub async fn update_title(&self, id: Uuid, title: String) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.update_title(id, title).await)
+            .await
+    }
+
+    /// Update the scheduled_for date of a todo.
+    pub async fn update_scheduled_for(
+        &self,
+        id: Uuid,
+        scheduled_for: Option<NaiveDate>,
+    ) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.update_scheduled_for(id, scheduled_for).await)
+            .await
+    }
+
+    /// Update the notes of a todo.
+    pub async fn update_notes(&self, id: Uuid, notes: Option<String>) -> Result<todo::Model> {
+        self.transaction(async |txn| txn.update_notes(id, notes).await)
+            .await
+    }
+
+    /// Reorder within a column/group (pending or done), touching only the
+    /// moved todo unless its neighbours have run out of room.
+    pub async fn reorder(&self, id: Uuid, direction: ReorderDirection) -> Result<()> {
+        self.transaction(async |txn| txn.reorder(id, direction).await)
+            .await
+    }
+}
+
+/// An open write transaction over the todos table, handed to the closure
+/// passed to [`TodoService::transaction`]. Its mutations behave like the
+/// [`TodoService`] methods of the same name but only take effect together,
+/// when the closure returns `Ok`.
+pub struct TodoTransaction {
+    txn: DatabaseTransaction,
+    clock: SharedClock,
+}
+
+impl TodoTransaction {
+    /// Insert a todo at `slot` in its day, or in backlog column
+    /// `backlog_column` when it isn't scheduled.
+    pub async fn add(
+        &self,
+        title: impl Into<String>,
+        scheduled_for: Option<NaiveDate>,
+        backlog_column: i64,
+        notes: Option<String>,
+        slot: Slot,
+    ) -> Result<todo::Model> {
+        let group = Group::pending(scheduled_for, backlog_column);
+        let order_index = slot_index(&self.txn, group, None, slot).await?;
+        let now = self.clock.now();
+
+        let model = todo::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            title: Set(title.into()),
+            status: Set("pending".to_string()),
+            scheduled_for: Set(scheduled_for),
+            backlog_column: Set(backlog_column),
+            order_index: Set(order_index),
+            notes: Set(notes),
+            metadata: Set(JsonValue::Null),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        model.insert(&self.txn).await.into_diagnostic()
+    }
+
+    /// Fetch a todo, seeing this transaction's own changes.
+    pub async fn get(&self, id: Uuid) -> Result<todo::Model> {
+        load(&self.txn, id).await
+    }
+
     pub async fn delete(&self, id: Uuid) -> Result<bool> {
         let res = todo::Entity::delete_by_id(id)
-            .exec(&self.db)
+            .exec(&self.txn)
             .await
             .into_diagnostic()?;
 
         Ok(res.rows_affected > 0)
     }
 
-    /// Mark a todo as complete, ensuring backlog items move into today's column.
+    pub async fn delete_many(&self, ids: &[Uuid]) -> Result<u64> {
+        let res = todo::Entity::delete_many()
+            .filter(todo::Column::Id.is_in(ids.iter().copied()))
+            .exec(&self.txn)
+            .await
+            .into_diagnostic()?;
+
+        Ok(res.rows_affected)
+    }
+
     pub async fn mark_done(&self, id: Uuid, today: NaiveDate) -> Result<todo::Model> {
-        let model = self.load(id).await?;
+        let model = load(&self.txn, id).await?;
 
         if model.status == STATUS_DONE {
             return Ok(model);
         }
 
         let scheduled_for = model.scheduled_for.or(Some(today));
+        let group = Group {
+            date: scheduled_for,
+            done: true,
+            ..Group::of(&model)
+        };
 
-        let order_index = self.next_done_order_index(scheduled_for).await?;
+        let order_index = slot_index(&self.txn, group, Some(model.id), Slot::Bottom).await?;
 
         let mut active: todo::ActiveModel = model.into();
 
@@ -128,254 +686,413 @@ impl TodoService {
         active.scheduled_for = Set(scheduled_for);
         active.order_index = Set(order_index);
 
-        active.update(&self.db).await.into_diagnostic()
+        self.update(active).await
     }
 
-    /// Revert a completed todo back to a pending state.
     pub async fn mark_pending(&self, id: Uuid) -> Result<todo::Model> {
-        let model = self.load(id).await?;
+        let model = load(&self.txn, id).await?;
 
         if model.status != STATUS_DONE {
             return Ok(model);
         }
 
-        let scope = model.scheduled_for;
-        let target_index = self.next_top_order_index(scope).await?;
+        let group = Group::pending(model.scheduled_for, model.backlog_column);
+        let target_index = slot_index(&self.txn, group, Some(model.id), Slot::Top).await?;
 
         let mut active: todo::ActiveModel = model.into();
         active.status = Set("pending".to_string());
         active.order_index = Set(target_index);
 
-        active.update(&self.db).await.into_diagnostic()
+        self.update(active).await
     }
 
-    /// Move overdue todos (scheduled in the past) to today.
-    pub async fn rollover_to(&self, today: NaiveDate) -> Result<usize> {
+    pub async fn rollover_to(&self, today: NaiveDate) -> Result<Vec<Uuid>> {
         let overdue = todo::Entity::find()
             .filter(todo::Column::ScheduledFor.lt(today))
             .filter(todo::Column::ScheduledFor.is_not_null())
             .filter(todo::Column::Status.ne(STATUS_DONE))
+            .order_by_asc(todo::Column::ScheduledFor)
             .order_by_asc(todo::Column::OrderIndex)
-            .all(&self.db)
+            .all(&self.txn)
             .await
             .into_diagnostic()?;
 
-        if overdue.is_empty() {
-            return Ok(0);
-        }
-
-        let mut next_index = self.next_pending_bottom_index(Some(today)).await?;
-        let mut moved = 0usize;
+        let group = Group::pending(Some(today), 0);
+        let mut moved = Vec::with_capacity(overdue.len());
 
         for model in overdue {
-            next_index += 1;
+            let index = slot_index(&self.txn, group, Some(model.id), Slot::Bottom).await?;
+
+            moved.push(model.id);
+
+            let metadata = match model.scheduled_for {
+                Some(from) => todo::with_rollover(model.metadata.clone(), from, today),
+                None => model.metadata.clone(),
+            };
 
             let mut active: todo::ActiveModel = model.into();
 
+            active.metadata = Set(metadata);
             active.scheduled_for = Set(Some(today));
-            active.order_index = Set(next_index);
-            active.update(&self.db).await.into_diagnostic()?;
-
-            moved += 1;
+            active.order_index = Set(index);
+            self.update(active).await?;
         }
 
         Ok(moved)
     }
 
-    /// Move a todo to another column (day/backlog) placing it at the top or bottom.
     pub async fn move_to_scope(
         &self,
         id: Uuid,
         scope: ListScope,
         placement: MovePlacement,
     ) -> Result<todo::Model> {
-        let model = self.load(id).await?;
-        let target_date = scope_to_date(scope);
-
-        let target_index = match placement {
-            MovePlacement::Top => self.next_top_order_index(target_date).await?,
-            MovePlacement::Bottom => {
-                if model.status == STATUS_DONE {
-                    self.next_done_order_index(target_date).await?
-                } else {
-                    self.next_pending_bottom_index(target_date).await?
-                }
-            }
+        let model = load(&self.txn, id).await?;
+        let group = Group::of(&model).in_scope(scope);
+
+        let slot = match placement {
+            MovePlacement::Top => Slot::Top,
+            MovePlacement::Bottom => Slot::Bottom,
         };
 
-        let mut active: todo::ActiveModel = model.clone().into();
+        self.place(model, group, slot).await
+    }
 
-        active.scheduled_for = Set(target_date);
-        active.order_index = Set(target_index);
+    /// Move a todo to `slot` in a backlog column, or in its own column when
+    /// `column` is `None`.
+    pub async fn move_to_backlog(
+        &self,
+        id: Uuid,
+        column: Option<i64>,
+        slot: Slot,
+    ) -> Result<todo::Model> {
+        let model = load(&self.txn, id).await?;
+        let group = Group {
+            backlog_column: column.unwrap_or(model.backlog_column),
+            ..Group::of(&model).in_scope(ListScope::Backlog)
+        };
+
+        self.place(model, group, slot).await
+    }
 
-        let updated = active.update(&self.db).await.into_diagnostic()?;
+    pub async fn move_to_position(
+        &self,
+        id: Uuid,
+        scope: ListScope,
+        position: usize,
+    ) -> Result<todo::Model> {
+        let model = load(&self.txn, id).await?;
+        let group = Group::of(&model).in_scope(scope);
 
-        Ok(updated)
+        self.place(model, group, Slot::At(position)).await
     }
 
-    /// Update the backlog_column field for a backlog item.
-    pub async fn set_backlog_column(&self, id: Uuid, column: i64) -> Result<todo::Model> {
-        let model = self.load(id).await?;
+    pub async fn reorder(&self, id: Uuid, direction: ReorderDirection) -> Result<()> {
+        let model = load(&self.txn, id).await?;
+        let group = Group::of(&model);
 
-        let mut active: todo::ActiveModel = model.into();
-        active.backlog_column = Set(column);
+        let siblings = group.select().all(&self.txn).await.into_diagnostic()?;
+
+        let Some(idx) = siblings.iter().position(|t| t.id == id) else {
+            bail!("todo {} no longer exists", id);
+        };
 
-        active.update(&self.db).await.into_diagnostic()
+        // Positions count the other todos in the group, so moving down one
+        // lands after the todo that currently follows.
+        let position = match direction {
+            ReorderDirection::Up if idx > 0 => idx - 1,
+            ReorderDirection::Down if idx + 1 < siblings.len() => idx + 1,
+            _ => return Ok(()),
+        };
+
+        self.place(model, group, Slot::At(position)).await?;
+
+        Ok(())
     }
 
-    /// Get a todo by id.
-    pub async fn get(&self, id: Uuid) -> Result<todo::Model> {
-        self.load(id).await
+    /// File every todo from backlog column `from` under `into`, appending
+    /// the backlog ones to the bottom of `into` in their current order.
+    /// With `and_beyond`, todos in columns past `from` come along too.
+    /// Returns how many backlog todos moved.
+    pub async fn merge_backlog_column(
+        &self,
+        from: i64,
+        into: i64,
+        and_beyond: bool,
+    ) -> Result<usize> {
+        let column = if and_beyond {
+            todo::Column::BacklogColumn.gte(from)
+        } else {
+            todo::Column::BacklogColumn.eq(from)
+        };
+
+        let models = todo::Entity::find()
+            .filter(column)
+            .order_by_asc(todo::Column::BacklogColumn)
+            .order_by_asc(todo::Column::OrderIndex)
+            .all(&self.txn)
+            .await
+            .into_diagnostic()?;
+
+        let mut moved = 0;
+
+        for model in models {
+            let mut update = todo::Entity::update_many()
+                .col_expr(todo::Column::BacklogColumn, Expr::value(into))
+                .filter(todo::Column::Id.eq(model.id));
+
+            if model.scheduled_for.is_none() {
+                let group = Group {
+                    backlog_column: into,
+                    ..Group::of(&model)
+                };
+                let order_index =
+                    slot_index(&self.txn, group, Some(model.id), Slot::Bottom).await?;
+
+                update = update.col_expr(todo::Column::OrderIndex, Expr::value(order_index));
+                moved += 1;
+            }
+
+            // The column changed, not the todo: leave `updated_at` alone.
+            update.exec(&self.txn).await.into_diagnostic()?;
+        }
+
+        Ok(moved)
     }
 
-    /// Update the title of a todo.
-    pub async fn update_title(&self, id: Uuid, title: String) -> Result<todo::Model> {
-        let model = self.load(id).await?;
+    /// Renumber backlog columns after they were reordered or one was
+    /// removed: the todos in column `order[i]` end up in column `i`.
+    pub async fn renumber_backlog_columns(&self, order: &[i64]) -> Result<()> {
+        let arms: String = order
+            .iter()
+            .enumerate()
+            .filter(|&(new, &old)| new as i64 != old)
+            .map(|(new, old)| format!(" WHEN {old} THEN {new}"))
+            .collect();
+
+        if arms.is_empty() {
+            return Ok(());
+        }
+
+        todo::Entity::update_many()
+            .col_expr(
+                todo::Column::BacklogColumn,
+                Expr::cust(format!("CASE backlog_column{arms} ELSE backlog_column END")),
+            )
+            .filter(todo::Column::BacklogColumn.is_in(order.iter().copied()))
+            .exec(&self.txn)
+            .await
+            .into_diagnostic()?;
+
+        Ok(())
+    }
+
+    /// The underlying transaction, for writing other tables (such as
+    /// config) in the same commit.
+    pub(crate) fn connection(&self) -> &DatabaseTransaction {
+        &self.txn
+    }
+
+    /// Add or remove an already normalized tag.
+    pub async fn set_tag(&self, id: Uuid, tag: &str, add: bool) -> Result<todo::Model> {
+        let model = load(&self.txn, id).await?;
+        let mut tags = model.tags();
+
+        if add && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        } else if !add {
+            tags.retain(|t| t != tag);
+        }
+
+        let metadata = todo::with_tags(model.metadata.clone(), &tags);
         let mut active: todo::ActiveModel = model.into();
+
+        active.metadata = Set(metadata);
+
+        self.update(active).await
+    }
+
+    pub async fn update_title(&self, id: Uuid, title: String) -> Result<todo::Model> {
+        let mut active: todo::ActiveModel = load(&self.txn, id).await?.into();
         active.title = Set(title);
-        active.update(&self.db).await.into_diagnostic()
+        self.update(active).await
     }
 
-    /// Update the scheduled_for date of a todo.
     pub async fn update_scheduled_for(
         &self,
         id: Uuid,
         scheduled_for: Option<NaiveDate>,
     ) -> Result<todo::Model> {
-        let model = self.load(id).await?;
-        let mut active: todo::ActiveModel = model.into();
+        let mut active: todo::ActiveModel = load(&self.txn, id).await?.into();
         active.scheduled_for = Set(scheduled_for);
-        active.update(&self.db).await.into_diagnostic()
+        self.update(active).await
     }
 
-    /// Update the notes of a todo.
     pub async fn update_notes(&self, id: Uuid, notes: Option<String>) -> Result<todo::Model> {
-        let model = self.load(id).await?;
-        let mut active: todo::ActiveModel = model.into();
+        let mut active: todo::ActiveModel = load(&self.txn, id).await?.into();
         active.notes = Set(notes);
-        active.update(&self.db).await.into_diagnostic()
+        self.update(active).await
     }
 
-    /// Reorder within a column/group (pending or done).
-    pub async fn reorder(&self, id: Uuid, direction: ReorderDirection) -> Result<()> {
-        let model = self.load(id).await?;
+    /// Put `model` into `group` at `slot`, saving its new date, column and
+    /// index.
+    async fn place(&self, model: todo::Model, group: Group, slot: Slot) -> Result<todo::Model> {
+        let order_index = slot_index(&self.txn, group, Some(model.id), slot).await?;
 
-        let scope = match model.scheduled_for {
-            Some(date) => ListScope::Day(date),
-            None => ListScope::Backlog,
-        };
+        let mut active: todo::ActiveModel = model.into();
 
-        let status = if model.status == STATUS_DONE {
-            StatusFilter::Done
-        } else {
-            StatusFilter::Pending
-        };
+        active.scheduled_for = Set(group.date);
+        active.backlog_column = Set(group.backlog_column);
+        active.order_index = Set(order_index);
 
-        let mut tasks = self
-            .column_query(scope, status)
-            .all(&self.db)
-            .await
-            .into_diagnostic()?;
+        self.update(active).await
+    }
 
-        let Some(idx) = tasks.iter().position(|t| t.id == id) else {
-            bail!("todo {} no longer exists", id);
-        };
+    /// Save `active`, stamping `updated_at` from the service clock.
+    async fn update(&self, mut active: todo::ActiveModel) -> Result<todo::Model> {
+        active.updated_at = Set(self.clock.now());
 
-        match direction {
-            ReorderDirection::Up if idx > 0 => tasks.swap(idx, idx - 1),
-            ReorderDirection::Down if idx + 1 < tasks.len() => tasks.swap(idx, idx + 1),
-            _ => return Ok(()),
-        }
+        active.update(&self.txn).await.into_diagnostic()
+    }
+}
 
-        for (index, task) in tasks.into_iter().enumerate() {
-            let mut active: todo::ActiveModel = task.into();
+async fn load<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<todo::Model> {
+    todo::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .into_diagnostic()?
+        .ok_or_else(|| miette::miette!("todo {id} not found"))
+}
+
+/// Where a todo goes among the todos it is ordered with (its day or
+/// backlog column, pending and done apart).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Top,
+    Bottom,
+    /// Before the todo currently at this position (0-based), not counting
+    /// the todo being placed.
+    At(usize),
+}
 
-            active.order_index = Set(index as i64);
+/// The todos a todo is ordered against: one day (or backlog column) and
+/// one status, since done todos always sort after pending ones.
+#[derive(Debug, Clone, Copy)]
+struct Group {
+    date: Option<NaiveDate>,
+    backlog_column: i64,
+    done: bool,
+}
 
-            active.update(&self.db).await.into_diagnostic()?;
+impl Group {
+    fn of(model: &todo::Model) -> Self {
+        Self {
+            date: model.scheduled_for,
+            backlog_column: model.backlog_column,
+            done: model.status == STATUS_DONE,
         }
+    }
 
-        Ok(())
+    fn pending(date: Option<NaiveDate>, backlog_column: i64) -> Self {
+        Self {
+            date,
+            backlog_column,
+            done: false,
+        }
     }
 
-    async fn load(&self, id: Uuid) -> Result<todo::Model> {
-        todo::Entity::find_by_id(id)
-            .one(&self.db)
-            .await
-            .into_diagnostic()?
-            .ok_or_else(|| miette::miette!("todo {id} not found"))
+    fn in_scope(self, scope: ListScope) -> Self {
+        Self {
+            date: scope_to_date(scope),
+            ..self
+        }
     }
 
-    fn column_query(
-        &self,
-        scope: ListScope,
-        status: StatusFilter,
-    ) -> sea_orm::Select<todo::Entity> {
-        let mut query = todo::Entity::find().filter(scope_condition(scope));
-
-        query = match status {
-            StatusFilter::Pending => query.filter(todo::Column::Status.ne(STATUS_DONE)),
-            StatusFilter::Done => query.filter(todo::Column::Status.eq(STATUS_DONE)),
-            StatusFilter::Any => query,
+    fn select(&self) -> sea_orm::Select<todo::Entity> {
+        let query = match self.date {
+            Some(date) => todo::Entity::find().filter(todo::Column::ScheduledFor.eq(date)),
+            None => todo::Entity::find()
+                .filter(todo::Column::ScheduledFor.is_null())
+                .filter(todo::Column::BacklogColumn.eq(self.backlog_column)),
+        };
+
+        let query = if self.done {
+            query.filter(todo::Column::Status.eq(STATUS_DONE))
+        } else {
+            query.filter(todo::Column::Status.ne(STATUS_DONE))
         };
 
         query.order_by_asc(todo::Column::OrderIndex)
     }
+}
 
-    async fn next_top_order_index(&self, scope_date: Option<NaiveDate>) -> Result<i64> {
-        match self
-            .find_order_index(scope_date, StatusFilter::Pending, Extremum::Min)
-            .await?
-        {
-            Some(min) => Ok(min - 1),
-            None => Ok(0),
-        }
+/// Pick an `order_index` for `slot` in `group`, ignoring `exclude` (the
+/// todo being moved). Usually this is the midpoint of the new neighbours;
+/// when they are adjacent the group is respaced first.
+async fn slot_index<C: ConnectionTrait>(
+    db: &C,
+    group: Group,
+    exclude: Option<Uuid>,
+    slot: Slot,
+) -> Result<i64> {
+    let siblings: Vec<todo::Model> = group
+        .select()
+        .all(db)
+        .await
+        .into_diagnostic()?
+        .into_iter()
+        .filter(|model| Some(model.id) != exclude)
+        .collect();
+
+    let position = match slot {
+        Slot::Top => 0,
+        Slot::Bottom => siblings.len(),
+        Slot::At(position) => position.min(siblings.len()),
+    };
+
+    let before = position.checked_sub(1).map(|idx| siblings[idx].order_index);
+    let after = siblings.get(position).map(|model| model.order_index);
+
+    if let Some(index) = index_between(before, after) {
+        return Ok(index);
     }
 
-    async fn next_pending_bottom_index(&self, scope_date: Option<NaiveDate>) -> Result<i64> {
-        Ok(self
-            .find_order_index(scope_date, StatusFilter::Pending, Extremum::Max)
-            .await?
-            .map(|max| max + 1)
-            .unwrap_or(0))
-    }
+    respace(db, &siblings, position).await
+}
 
-    async fn next_done_order_index(&self, scope_date: Option<NaiveDate>) -> Result<i64> {
-        Ok(self
-            .find_order_index(scope_date, StatusFilter::Any, Extremum::Max)
-            .await?
-            .map(|max| max + 1)
-            .unwrap_or(0))
+fn index_between(before: Option<i64>, after: Option<i64>) -> Option<i64> {
+    match (before, after) {
+        (None, None) => Some(ORDER_BASE),
+        (Some(before), None) => before.checked_add(ORDER_GAP),
+        // Keep indexes positive: step up from zero once the top is crowded.
+        (None, Some(after)) if after > ORDER_GAP => Some(after - ORDER_GAP),
+        (None, Some(after)) => index_between(Some(0), Some(after)),
+        (Some(before), Some(after)) => (after - before > 1).then(|| before + (after - before) / 2),
     }
+}
 
-    async fn find_order_index(
-        &self,
-        scope_date: Option<NaiveDate>,
-        status: StatusFilter,
-        extremum: Extremum,
-    ) -> Result<Option<i64>> {
-        let mut query = todo::Entity::find().filter(scope_condition(match scope_date {
-            Some(date) => ListScope::Day(date),
-            None => ListScope::Backlog,
-        }));
-
-        query = match status {
-            StatusFilter::Any => query,
-            StatusFilter::Pending => query.filter(todo::Column::Status.ne(STATUS_DONE)),
-            StatusFilter::Done => query.filter(todo::Column::Status.eq(STATUS_DONE)),
-        };
-
-        query = match extremum {
-            Extremum::Min => query.order_by_asc(todo::Column::OrderIndex),
-            Extremum::Max => query.order_by_desc(todo::Column::OrderIndex),
-        };
-
-        Ok(query
-            .one(&self.db)
+/// Renumber `siblings` `ORDER_GAP` apart, leaving a hole at `position`,
+/// and return the index of the hole.
+async fn respace<C: ConnectionTrait>(
+    db: &C,
+    siblings: &[todo::Model],
+    position: usize,
+) -> Result<i64> {
+    let index_at = |slot: usize| ORDER_BASE + slot as i64 * ORDER_GAP;
+
+    for (idx, model) in siblings.iter().enumerate() {
+        let slot = if idx < position { idx } else { idx + 1 };
+
+        // Renumbering isn't an edit, so leave `updated_at` alone.
+        todo::Entity::update_many()
+            .col_expr(todo::Column::OrderIndex, Expr::value(index_at(slot)))
+            .filter(todo::Column::Id.eq(model.id))
+            .exec(db)
             .await
-            .into_diagnostic()?
-            .map(|model| model.order_index))
+            .into_diagnostic()?;
     }
+
+    Ok(index_at(position))
 }
 
 fn scope_condition(scope: ListScope) -> Condition {
@@ -385,22 +1102,36 @@ fn scope_condition(scope: ListScope) -> Condition {
     }
 }
 
+/// Parse `+tag` / `tag` (add) and `-tag` (remove) tokens into normalized
+/// tags and whether to add them.
+pub fn tag_edits<S: AsRef<str>>(tokens: &[S]) -> Result<Vec<(String, bool)>> {
+    tokens
+        .iter()
+        .map(|token| {
+            let token = token.as_ref();
+            let (add, tag) = match token.strip_prefix('-') {
+                Some(tag) => (false, tag),
+                None => (true, token.strip_prefix('+').unwrap_or(token)),
+            };
+            let tag = normalize_tag(tag);
+
+            if tag.is_empty() {
+                bail!("tag must not be empty");
+            }
+
+            Ok((tag, add))
+        })
+        .collect()
+}
+
+/// Tags are stored lowercase without a leading `#`.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
 fn scope_to_date(scope: ListScope) -> Option<NaiveDate> {
     match scope {
         ListScope::Day(date) => Some(date),
         ListScope::Backlog => None,
     }
 }
-
-#[derive(Debug, Clone, Copy)]
-enum StatusFilter {
-    Pending,
-    Done,
-    Any,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Extremum {
-    Min,
-    Max,
-}
@@ -0,0 +1,58 @@
+//! Notice commits made outside the app, such as `mach add` in another
+//! shell, so the TUI can reload.
+
+use miette::{IntoDiagnostic, Result};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, SqlxSqliteConnector, Statement,
+    sqlx::sqlite::SqlitePoolOptions,
+};
+
+/// Polls SQLite's `data_version`, which changes whenever a connection other
+/// than the one asking commits. The watcher keeps a connection of its own,
+/// so the app's own writes register as changes too.
+pub struct ChangeWatcher {
+    conn: DatabaseConnection,
+    version: i64,
+}
+
+impl ChangeWatcher {
+    /// Open a watch connection to the same database as `db`.
+    pub async fn new(db: &DatabaseConnection) -> Result<Self> {
+        let options = db.get_sqlite_connection_pool().connect_options();
+
+        // The version is per connection, so it must never be swapped out.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with((*options).clone())
+            .await
+            .into_diagnostic()?;
+
+        let conn = SqlxSqliteConnector::from_sqlx_sqlite_pool(pool);
+        let version = data_version(&conn).await?;
+
+        Ok(Self { conn, version })
+    }
+
+    /// Whether anything was committed since the last check.
+    pub async fn changed(&mut self) -> Result<bool> {
+        let version = data_version(&self.conn).await?;
+
+        Ok(std::mem::replace(&mut self.version, version) != version)
+    }
+}
+
+async fn data_version(conn: &DatabaseConnection) -> Result<i64> {
+    let row = conn
+        .query_one_raw(Statement::from_string(
+            DbBackend::Sqlite,
+            "PRAGMA data_version",
+        ))
+        .await
+        .into_diagnostic()?
+        .ok_or_else(|| miette::miette!("PRAGMA data_version returned no row"))?;
+
+    row.try_get_by_index(0).into_diagnostic()
+}
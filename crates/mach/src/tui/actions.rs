@@ -1,16 +1,36 @@
-use chrono::Duration as ChronoDuration;
+use std::collections::HashSet;
+
+use chrono::{Duration as ChronoDuration, NaiveDate};
+use miette::{Context, IntoDiagnostic};
 use uuid::Uuid;
 
-use crate::service::config::WeekStart;
-use crate::service::todo::{ListOptions, ListScope, MovePlacement, ReorderDirection};
+use crate::dates::parse_date;
+use crate::editor;
+use crate::entity::todo;
+use crate::service::settings;
+use crate::service::todo::{
+    ListOptions, ListScope, MovePlacement, ReorderDirection, Slot, TodoQuery,
+};
 
 use super::App;
 use super::cursor::{CursorState, Horizontal, Selection};
-use super::modes::{AddTarget, AddTodoState, DetailField, DetailState, SettingsState, UiMode};
-use super::state::{BACKLOG_COLUMNS, BoardData, TodoView, WeekState};
+use super::markdown;
+use super::modes::{
+    ActiveFilter, AddTarget, AddTodoState, DetailField, DetailState, PromptKind, PromptState,
+    RolloverAction, RolloverState, SettingsState, UiMode,
+};
+use super::palette::{self, Palette};
+use super::state::{BoardData, TodoView, WeekState};
+use super::status::ReportExt;
+use super::terminal::{Tui, resume_terminal, suspend_terminal};
+use super::text_input::TextInput;
 
 impl App {
     pub fn refresh_board(&mut self) -> miette::Result<()> {
+        self.mark_changes_seen();
+
+        let matching = self.filter_matches()?;
+
         for (idx, column) in self.state.columns.iter().enumerate() {
             let opts = ListOptions {
                 scope: ListScope::Day(column.date),
@@ -18,12 +38,13 @@ impl App {
             };
 
             let todos = self.runtime.block_on(self.services.todos.list(opts))?;
+            let todos = keep_matching(todos, matching.as_ref());
 
             self.board
                 .set_day(idx, todos.into_iter().map(TodoView::from).collect());
         }
 
-        self.refresh_backlog()?;
+        self.load_backlog(matching.as_ref())?;
 
         self.cursor
             .sync_after_refresh(self.state.columns.len(), &self.board);
@@ -32,6 +53,14 @@ impl App {
     }
 
     pub fn refresh_backlog(&mut self) -> miette::Result<()> {
+        self.mark_changes_seen();
+
+        let matching = self.filter_matches()?;
+
+        self.load_backlog(matching.as_ref())
+    }
+
+    fn load_backlog(&mut self, matching: Option<&HashSet<Uuid>>) -> miette::Result<()> {
         let all_backlog = self
             .runtime
             .block_on(self.services.todos.list(ListOptions {
@@ -39,22 +68,151 @@ impl App {
                 include_done: true,
             }))?;
 
-        let mut columns: [Vec<TodoView>; BACKLOG_COLUMNS] = Default::default();
+        let all_backlog = keep_matching(all_backlog, matching);
+
+        let titles = self.runtime.block_on(self.services.backlog.columns())?;
+        let mut columns = vec![Vec::new(); titles.len()];
 
+        // Todos filed under a column that no longer exists show in the last.
         for todo in all_backlog {
-            let col = (todo.backlog_column as usize).min(BACKLOG_COLUMNS - 1);
+            let col = (todo.backlog_column.max(0) as usize).min(titles.len() - 1);
             columns[col].push(TodoView::from(todo));
         }
 
-        for (col, items) in columns.into_iter().enumerate() {
-            self.board.set_backlog_column(col, items);
-        }
+        self.board.set_backlog(titles, columns);
 
         self.backlog_cursor.sync_after_refresh(&self.board);
 
         Ok(())
     }
 
+    /// Note the current database version before reading, so the tick only
+    /// reloads for writes made after this refresh (our own included).
+    fn mark_changes_seen(&mut self) {
+        if let Some(watcher) = self.watcher.as_mut() {
+            self.runtime.block_on(watcher.changed()).ok();
+        }
+    }
+
+    /// Reload after another process wrote to the database, keeping the
+    /// cursors on the todos they were on.
+    pub fn handle_external_change(&mut self) -> miette::Result<()> {
+        let day_focus = self.cursor.current_todo_id(&self.board);
+        let backlog_focus = self.backlog_cursor.current_todo_id(&self.board);
+
+        self.refresh_board()?;
+        self.status.info("Reloaded changes made elsewhere");
+
+        if let Some((col, row)) = day_focus.and_then(|id| self.board.find_day_position(id))
+            && col == self.cursor.focus
+        {
+            self.cursor.day_rows[col] = row;
+        }
+
+        if let Some((col, row)) = backlog_focus.and_then(|id| self.board.find_backlog_position(id))
+            && col == self.backlog_cursor.column
+        {
+            self.backlog_cursor.rows[col] = row;
+        }
+
+        Ok(())
+    }
+
+    /// Move the app onto a new day after midnight: roll overdue todos onto
+    /// it, follow it to the next week if today was on screen, and keep the
+    /// cursor on the todo it was on.
+    pub fn handle_day_change(&mut self, today: NaiveDate) -> miette::Result<()> {
+        let previous = self.services.today();
+        let focused = self.current_target_id();
+
+        self.runtime.block_on(self.services.start_day(today))?;
+
+        let follow_week =
+            self.state.column_index(previous).is_some() && self.state.column_index(today).is_none();
+
+        if follow_week {
+            self.state = WeekState::new(today, self.week_pref);
+            self.board.reset(self.state.columns.len());
+        }
+
+        self.refresh_board()?;
+
+        if let Some((col, row)) = focused.and_then(|id| self.board.find_day_position(id)) {
+            self.cursor.focus = col;
+            self.cursor.day_rows[col] = row;
+        } else if follow_week && let Some(col) = self.state.column_index(today) {
+            self.cursor.focus = col;
+        }
+
+        let rolled = self.runtime.block_on(self.services.rolled_over())?.len();
+
+        if rolled > 0 {
+            self.status
+                .info(format!("New day: rolled over {}", count(rolled)));
+        }
+
+        Ok(())
+    }
+
+    /// Show the week containing `date` with the cursor on that day.
+    pub fn jump_to_date(&mut self, date: NaiveDate) -> miette::Result<()> {
+        self.state = WeekState::new(date, self.week_pref);
+        self.board.reset(self.state.columns.len());
+
+        self.refresh_board()?;
+
+        if let Some(col) = self.state.column_index(date) {
+            self.cursor.focus = col;
+            self.cursor.day_rows[col] = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Jump the board to a date expression typed into the `gd` prompt.
+    pub fn jump_to_input(&mut self, input: &str) -> miette::Result<()> {
+        let Some(date) = parse_date(input, self.services.today()) else {
+            return Err(miette::miette!("unrecognized date '{input}'"));
+        };
+
+        self.jump_to_date(date)
+    }
+
+    /// Ids of the todos matching the active `/` filter, if any. Run once
+    /// per refresh and shared by every column.
+    fn filter_matches(&self) -> miette::Result<Option<HashSet<Uuid>>> {
+        let Some(filter) = &self.filter else {
+            return Ok(None);
+        };
+
+        let matching = self
+            .runtime
+            .block_on(self.services.todos.query(&filter.query))?
+            .into_iter()
+            .map(|todo| todo.id)
+            .collect();
+
+        Ok(Some(matching))
+    }
+
+    /// Apply `/` filter text; empty text clears the filter.
+    pub fn set_filter(&mut self, text: &str) -> miette::Result<()> {
+        let text = text.trim();
+
+        self.filter = if text.is_empty() {
+            None
+        } else {
+            let query = TodoQuery::parse(text, self.services.today(), self.week_pref)?;
+
+            Some(ActiveFilter {
+                text: text.to_string(),
+                query,
+            })
+        };
+
+        self.refresh_board()
+    }
+
     pub fn current_target_id(&self) -> Option<Uuid> {
         self.cursor
             .selection
@@ -71,11 +229,13 @@ impl App {
 
     pub fn delete_current(&mut self) -> miette::Result<()> {
         if let Some(id) = self.current_target_id() {
+            let title = self.title_of(id);
             let deleted = self.runtime.block_on(self.services.todos.delete(id))?;
 
             if deleted {
                 self.cursor.selection = None;
                 self.refresh_board()?;
+                self.status.info(format!("Deleted '{title}'"));
             }
         }
         Ok(())
@@ -83,11 +243,13 @@ impl App {
 
     pub fn delete_backlog_current(&mut self) -> miette::Result<()> {
         if let Some(id) = self.backlog_current_target_id() {
+            let title = self.title_of(id);
             let deleted = self.runtime.block_on(self.services.todos.delete(id))?;
 
             if deleted {
                 self.backlog_cursor.selection = None;
                 self.refresh_backlog()?;
+                self.status.info(format!("Deleted '{title}'"));
             }
         }
         Ok(())
@@ -106,14 +268,18 @@ impl App {
 
             self.cursor.selection = None;
 
+            let title = self.title_of(id);
+
             if current_status == "done" {
                 self.runtime
                     .block_on(self.services.todos.mark_pending(id))?;
+                self.status.info(format!("Reopened '{title}'"));
             } else {
                 let today = self.services.today();
 
                 self.runtime
                     .block_on(self.services.todos.mark_done(id, today))?;
+                self.status.info(format!("Completed '{title}'"));
             }
 
             self.refresh_board()?;
@@ -147,14 +313,18 @@ impl App {
 
             self.backlog_cursor.selection = None;
 
+            let title = self.title_of(id);
+
             if current_status == "done" {
                 self.runtime
                     .block_on(self.services.todos.mark_pending(id))?;
+                self.status.info(format!("Reopened '{title}'"));
             } else {
                 let today = self.services.today();
 
                 self.runtime
                     .block_on(self.services.todos.mark_done(id, today))?;
+                self.status.info(format!("Completed '{title}'"));
             }
 
             self.refresh_board()?;
@@ -189,6 +359,7 @@ impl App {
             ))?;
 
             self.refresh_board()?;
+            self.moved_message(id, ListScope::Backlog);
         }
         Ok(())
     }
@@ -207,6 +378,7 @@ impl App {
         ))?;
 
         self.refresh_board()?;
+        self.moved_message(id, ListScope::Day(today));
 
         Ok(())
     }
@@ -225,6 +397,7 @@ impl App {
         ))?;
 
         self.refresh_board()?;
+        self.moved_message(id, ListScope::Day(tomorrow));
 
         Ok(())
     }
@@ -249,6 +422,7 @@ impl App {
         ))?;
 
         self.refresh_board()?;
+        self.moved_message(id, ListScope::Day(target_date));
 
         Ok(())
     }
@@ -339,50 +513,107 @@ impl App {
     }
 
     pub fn open_settings(&mut self) {
-        let settings = SettingsState {
-            week_start: self.week_pref,
+        let Some(entries) = self
+            .runtime
+            .block_on(self.services.config.entries())
+            .report(&mut self.status)
+        else {
+            return;
         };
 
-        self.ui_mode = UiMode::Settings(settings);
+        self.ui_mode = UiMode::Settings(SettingsState::new(entries));
     }
 
-    pub fn apply_week_start(&mut self, week_start: WeekStart) {
-        if week_start == self.week_pref {
-            return;
-        }
+    /// Store `input` for the setting `key` and apply it.
+    pub fn set_setting(&mut self, key: &str, input: &str) -> miette::Result<()> {
+        let value = self
+            .runtime
+            .block_on(self.services.config.set(key, input))?;
 
-        self.week_pref = week_start;
+        self.apply_preferences()?;
 
-        if let Err(err) = self
-            .runtime
-            .block_on(self.services.config.save_week_start(week_start))
-        {
-            eprintln!("failed to save week start preference: {err}");
+        let def = settings::lookup(key)?;
+
+        self.status.info(format!("{key} = {}", def.format(&value)));
+
+        Ok(())
+    }
+
+    /// Go back to the default for the setting `key`.
+    pub fn reset_setting(&mut self, key: &str) -> miette::Result<()> {
+        self.runtime.block_on(self.services.config.reset(key))?;
+
+        self.apply_preferences()?;
+        self.status.info(format!("{key} reset"));
+
+        Ok(())
+    }
+
+    /// Write every scheduled todo to the iCalendar file at `path`.
+    pub fn export_ics(&mut self, path: &str) -> miette::Result<()> {
+        let calendar = self.runtime.block_on(self.services.todos.export_ics())?;
+
+        std::fs::write(path, calendar.text)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("failed to write {path}"))?;
+
+        self.status
+            .info(format!("Exported {} todo(s) to {path}", calendar.events));
+
+        Ok(())
+    }
+
+    /// Re-read preferences after a settings change and update what the
+    /// board caches from them.
+    fn apply_preferences(&mut self) -> miette::Result<()> {
+        self.runtime.block_on(self.services.reload_preferences())?;
+
+        let week_start = self.services.week_start();
+
+        if week_start != self.week_pref {
+            self.week_pref = week_start;
+
+            self.state = WeekState::new(self.services.today(), week_start);
+            self.board = BoardData::new(self.state.columns.len());
+            self.cursor = CursorState::new(self.state.columns.len());
+
+            if let Some(idx) = self.state.column_index(self.services.today()) {
+                self.cursor.set_focus_row(idx, 0);
+            }
         }
 
-        self.state = WeekState::new(self.services.today(), week_start);
-        self.board = BoardData::new(self.state.columns.len());
-        self.cursor = CursorState::new(self.state.columns.len());
-        if let Some(idx) = self.state.column_index(self.services.today()) {
-            self.cursor.set_focus_row(idx, 0);
+        self.overflow_pref = self.services.title_overflow();
+
+        let (palette, problem) = Palette::resolve(
+            self.services.theme(),
+            self.services.color_overrides(),
+            palette::no_color(),
+        );
+
+        self.palette = palette;
+
+        if let UiMode::Settings(state) = &mut self.ui_mode {
+            state.entries = self.runtime.block_on(self.services.config.entries())?;
         }
 
-        self.refresh_board().ok();
+        self.refresh_board()?;
+
+        // The valid overrides are already drawn; report the bad ones.
+        match problem {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 
     pub fn open_add_todo_board(&mut self) {
         let target_date = self.state.columns[self.cursor.focus].date;
-        self.ui_mode = UiMode::AddTodo(AddTodoState {
-            input: String::new(),
-            target: AddTarget::Day(target_date),
-        });
+        self.ui_mode = UiMode::AddTodo(AddTodoState::new(AddTarget::Day(target_date)));
     }
 
     pub fn open_add_todo_backlog(&mut self) {
-        self.ui_mode = UiMode::AddTodo(AddTodoState {
-            input: String::new(),
-            target: AddTarget::BacklogColumn(self.backlog_cursor.column),
-        });
+        self.ui_mode = UiMode::AddTodo(AddTodoState::new(AddTarget::BacklogColumn(
+            self.backlog_cursor.column,
+        )));
     }
 
     pub fn submit_add_todo(&mut self, title: String, target: AddTarget) -> miette::Result<()> {
@@ -393,14 +624,16 @@ impl App {
                 self.refresh_board()?;
             }
             AddTarget::BacklogColumn(col) => {
-                let model = self
-                    .runtime
-                    .block_on(self.services.todos.add(&title, None, None))?;
                 self.runtime
-                    .block_on(self.services.todos.set_backlog_column(model.id, col as i64))?;
+                    .block_on(self.services.todos.transaction(async |txn| {
+                        txn.add(&title, None, col as i64, None, Slot::Top).await
+                    }))?;
                 self.refresh_backlog()?;
             }
         }
+
+        self.status.info(format!("Added '{title}'"));
+
         Ok(())
     }
 
@@ -419,7 +652,11 @@ impl App {
     }
 
     pub fn open_detail(&mut self, id: Uuid, from_backlog: bool) {
-        let Ok(model) = self.runtime.block_on(self.services.todos.get(id)) else {
+        let Some(model) = self
+            .runtime
+            .block_on(self.services.todos.get(id))
+            .report(&mut self.status)
+        else {
             return;
         };
 
@@ -432,6 +669,8 @@ impl App {
             field: DetailField::Title,
             editing: None,
             from_backlog,
+            raw_notes: false,
+            task_cursor: None,
         });
     }
 
@@ -443,15 +682,509 @@ impl App {
         let id = state.todo_id;
         let today = self.services.today();
 
-        if state.status == "done" {
-            if let Ok(model) = self.runtime.block_on(self.services.todos.mark_pending(id)) {
-                state.status = model.status;
+        let result = if state.status == "done" {
+            self.runtime.block_on(self.services.todos.mark_pending(id))
+        } else {
+            self.runtime
+                .block_on(self.services.todos.mark_done(id, today))
+        };
+
+        if let Some(model) = result.report(&mut self.status) {
+            state.status = model.status;
+        }
+    }
+
+    /// Save an edited detail field and show the stored value in the modal.
+    pub fn save_detail_field(
+        &mut self,
+        id: Uuid,
+        field: DetailField,
+        input: String,
+    ) -> miette::Result<()> {
+        let todos = &self.services.todos;
+
+        let model = match field {
+            DetailField::Title => {
+                let title = input.trim();
+
+                if title.is_empty() {
+                    miette::bail!("title must not be empty");
+                }
+
+                self.runtime
+                    .block_on(todos.update_title(id, title.to_string()))?
+            }
+            DetailField::Date => {
+                let input = input.trim();
+
+                let date = if input.is_empty()
+                    || input.eq_ignore_ascii_case("none")
+                    || input.eq_ignore_ascii_case("someday")
+                {
+                    None
+                } else {
+                    let Some(date) = parse_date(input, self.services.today()) else {
+                        miette::bail!("unrecognized date '{input}'");
+                    };
+
+                    Some(date)
+                };
+
+                self.runtime
+                    .block_on(todos.update_scheduled_for(id, date))?
             }
-        } else if let Ok(model) = self
+            DetailField::Notes => {
+                let notes = (!input.trim().is_empty()).then_some(input);
+
+                self.runtime.block_on(todos.update_notes(id, notes))?
+            }
+            DetailField::Status => return Ok(()),
+        };
+
+        if let UiMode::Detail(ref mut state) = self.ui_mode {
+            state.title = model.title;
+            state.date = model.scheduled_for;
+            state.notes = model.notes.unwrap_or_default();
+        }
+
+        self.status.info("Saved");
+
+        Ok(())
+    }
+
+    /// Flip the focused `- [ ]` checkbox in the open todo's notes.
+    pub fn toggle_detail_task(&mut self) {
+        let UiMode::Detail(ref mut state) = self.ui_mode else {
+            return;
+        };
+
+        let Some(task) = state.task_cursor else {
+            return;
+        };
+
+        let notes = markdown::toggle_task(&state.notes, task);
+
+        let saved = self.runtime.block_on(
+            self.services
+                .todos
+                .update_notes(state.todo_id, Some(notes.clone())),
+        );
+
+        if saved.report(&mut self.status).is_some() {
+            state.notes = notes;
+        }
+    }
+
+    /// Suspend the TUI and edit the open todo's notes in `$VISUAL`/`$EDITOR`.
+    pub fn edit_notes_in_editor(&mut self, terminal: &mut Tui) -> miette::Result<()> {
+        let UiMode::Detail(ref state) = self.ui_mode else {
+            return Ok(());
+        };
+
+        let id = state.todo_id;
+        let current = state.notes.clone();
+
+        suspend_terminal();
+
+        let edited = editor::edit_text(&current);
+
+        resume_terminal(terminal)?;
+
+        // Only a broken terminal ends the app; editor and save errors are
+        // shown in the status bar.
+        let Some(contents) = edited.report(&mut self.status) else {
+            return Ok(());
+        };
+
+        let notes = editor::notes_from_editor(contents);
+
+        let saved = self
             .runtime
-            .block_on(self.services.todos.mark_done(id, today))
+            .block_on(self.services.todos.update_notes(id, notes.clone()));
+
+        if saved.report(&mut self.status).is_some()
+            && let UiMode::Detail(ref mut state) = self.ui_mode
         {
-            state.status = model.status;
+            state.notes = notes.unwrap_or_default();
+            state.field = DetailField::Notes;
+        }
+
+        Ok(())
+    }
+
+    /// Ids a bulk action applies to: marked todos in board order, or else the
+    /// selected/focused todo.
+    pub fn bulk_target_ids(&self, backlog: bool) -> Vec<Uuid> {
+        let (marks, columns) = if backlog {
+            (
+                &self.backlog_cursor.marks,
+                self.board.backlog_columns.as_slice(),
+            )
+        } else {
+            (&self.cursor.marks, self.board.days.as_slice())
+        };
+
+        if marks.is_empty() {
+            let current = if backlog {
+                self.backlog_current_target_id()
+            } else {
+                self.current_target_id()
+            };
+
+            return current.into_iter().collect();
+        }
+
+        columns
+            .iter()
+            .flatten()
+            .map(|todo| todo.id)
+            .filter(|id| marks.contains(*id))
+            .collect()
+    }
+
+    pub fn toggle_mark(&mut self) {
+        let focus = self.cursor.focus;
+
+        if let Some(row) = self.cursor.row_for(focus, &self.board)
+            && let Some(id) = self.board.day_todo_id_at(focus, row)
+        {
+            self.cursor.selection = None;
+            self.cursor.marks.toggle(id, focus, row);
+        }
+    }
+
+    /// Mark every todo between the last mark and the cursor in the focused day.
+    pub fn mark_range(&mut self) {
+        let focus = self.cursor.focus;
+
+        let Some(row) = self.cursor.row_for(focus, &self.board) else {
+            return;
+        };
+
+        let anchor = self.cursor.marks.anchor_row(focus).unwrap_or(row);
+        let (start, end) = (anchor.min(row), anchor.max(row));
+
+        let ids: Vec<Uuid> = (start..=end)
+            .filter_map(|r| self.board.day_todo_id_at(focus, r))
+            .collect();
+
+        self.cursor.selection = None;
+        self.cursor.marks.extend(ids, focus, row);
+    }
+
+    pub fn toggle_backlog_mark(&mut self) {
+        let col = self.backlog_cursor.column;
+
+        if let Some(row) = self.backlog_cursor.row_for(col, &self.board)
+            && let Some(id) = self.board.backlog_todo_id_at(col, row)
+        {
+            self.backlog_cursor.selection = None;
+            self.backlog_cursor.marks.toggle(id, col, row);
+        }
+    }
+
+    /// Mark every todo between the last mark and the cursor in the focused column.
+    pub fn mark_backlog_range(&mut self) {
+        let col = self.backlog_cursor.column;
+
+        let Some(row) = self.backlog_cursor.row_for(col, &self.board) else {
+            return;
+        };
+
+        let anchor = self.backlog_cursor.marks.anchor_row(col).unwrap_or(row);
+        let (start, end) = (anchor.min(row), anchor.max(row));
+
+        let ids: Vec<Uuid> = (start..=end)
+            .filter_map(|r| self.board.backlog_todo_id_at(col, r))
+            .collect();
+
+        self.backlog_cursor.selection = None;
+        self.backlog_cursor.marks.extend(ids, col, row);
+    }
+
+    fn clear_bulk_state(&mut self, backlog: bool) {
+        if backlog {
+            self.backlog_cursor.marks.clear();
+            self.backlog_cursor.selection = None;
+        } else {
+            self.cursor.marks.clear();
+            self.cursor.selection = None;
+        }
+    }
+
+    pub fn bulk_complete(&mut self, backlog: bool) -> miette::Result<()> {
+        let ids = self.bulk_target_ids(backlog);
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let today = self.services.today();
+
+        self.runtime
+            .block_on(self.services.todos.complete_many(&ids, today))?;
+
+        self.clear_bulk_state(backlog);
+        self.refresh_board()?;
+        self.status.info(format!("Completed {}", count(ids.len())));
+
+        Ok(())
+    }
+
+    pub fn bulk_delete(&mut self, backlog: bool) -> miette::Result<()> {
+        let ids = self.bulk_target_ids(backlog);
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        self.runtime
+            .block_on(self.services.todos.delete_many(&ids))?;
+
+        self.clear_bulk_state(backlog);
+        self.refresh_board()?;
+        self.status.info(format!("Deleted {}", count(ids.len())));
+
+        Ok(())
+    }
+
+    pub fn bulk_move(&mut self, backlog: bool, scope: ListScope) -> miette::Result<()> {
+        let ids = self.bulk_target_ids(backlog);
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let placement = match scope {
+            ListScope::Day(_) => MovePlacement::Top,
+            ListScope::Backlog => MovePlacement::Bottom,
+        };
+
+        self.runtime.block_on(
+            self.services
+                .todos
+                .move_many_to_scope(&ids, scope, placement),
+        )?;
+
+        self.clear_bulk_state(backlog);
+        self.refresh_board()?;
+
+        let target = self.scope_label(scope);
+
+        self.status
+            .info(format!("Moved {} to {target}", count(ids.len())));
+
+        Ok(())
+    }
+
+    /// Apply `+tag` / `-tag` (bare names add) to the bulk targets.
+    pub fn bulk_tag(&mut self, backlog: bool, input: &str) -> miette::Result<()> {
+        let ids = self.bulk_target_ids(backlog);
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let tokens: Vec<&str> = input.split_whitespace().collect();
+
+        self.runtime
+            .block_on(self.services.todos.retag_many(&ids, &tokens))?;
+
+        self.clear_bulk_state(backlog);
+        self.refresh_board()?;
+        self.status.info(format!("Tagged {}", count(ids.len())));
+
+        Ok(())
+    }
+
+    pub fn bulk_reschedule(&mut self, backlog: bool, input: &str) -> miette::Result<()> {
+        let input = input.trim();
+
+        let scope = if input.eq_ignore_ascii_case("someday") || input.eq_ignore_ascii_case("none") {
+            ListScope::Backlog
+        } else {
+            let Some(date) = parse_date(input, self.services.today()) else {
+                miette::bail!("unrecognized date '{input}'");
+            };
+
+            ListScope::Day(date)
+        };
+
+        self.bulk_move(backlog, scope)
+    }
+
+    /// Show the todos that rolled over onto today, if any are still
+    /// pending there.
+    pub fn open_rollover_review(&mut self) -> miette::Result<()> {
+        let items: Vec<TodoView> = self
+            .runtime
+            .block_on(self.services.rolled_over())?
+            .into_iter()
+            .map(TodoView::from)
+            .collect();
+
+        if items.is_empty() {
+            return Ok(());
         }
+
+        self.ui_mode = UiMode::Rollover(RolloverState {
+            items,
+            cursor: 0,
+            reschedule: None,
+        });
+
+        Ok(())
+    }
+
+    /// Reschedule the highlighted rolled-over todo to a date expression
+    /// typed into the review.
+    pub fn reschedule_rollover(&mut self, input: &str) -> miette::Result<()> {
+        let input = input.trim();
+
+        let Some(date) = parse_date(input, self.services.today()) else {
+            miette::bail!("unrecognized date '{input}'");
+        };
+
+        self.resolve_rollover(RolloverAction::Reschedule(date))
+    }
+
+    /// Apply `action` to the highlighted rolled-over todo and drop it from
+    /// the review, closing it once every item is handled.
+    pub fn resolve_rollover(&mut self, action: RolloverAction) -> miette::Result<()> {
+        let UiMode::Rollover(state) = &mut self.ui_mode else {
+            return Ok(());
+        };
+
+        if state.cursor >= state.items.len() {
+            return Ok(());
+        }
+
+        let item = state.items.remove(state.cursor);
+        let (id, title) = (item.id, item.title);
+
+        state.cursor = state.cursor.min(state.items.len().saturating_sub(1));
+        state.reschedule = None;
+
+        if state.items.is_empty() {
+            self.ui_mode = UiMode::Board;
+        }
+
+        let todos = &self.services.todos;
+
+        let message = match action {
+            RolloverAction::Keep => return Ok(()),
+            RolloverAction::Backlog => {
+                self.runtime.block_on(todos.move_to_scope(
+                    id,
+                    ListScope::Backlog,
+                    MovePlacement::Bottom,
+                ))?;
+
+                format!("Moved '{title}' to backlog")
+            }
+            RolloverAction::Delete => {
+                self.runtime.block_on(todos.delete(id))?;
+
+                format!("Deleted '{title}'")
+            }
+            RolloverAction::Reschedule(date) => {
+                self.runtime.block_on(todos.move_to_scope(
+                    id,
+                    ListScope::Day(date),
+                    MovePlacement::Bottom,
+                ))?;
+
+                format!(
+                    "Moved '{title}' to {}",
+                    self.scope_label(ListScope::Day(date))
+                )
+            }
+        };
+
+        self.refresh_board()?;
+        self.status.info(message);
+
+        Ok(())
+    }
+
+    /// Title of a todo on screen, for status messages.
+    fn title_of(&self, id: Uuid) -> String {
+        self.board
+            .days
+            .iter()
+            .chain(&self.board.backlog_columns)
+            .flatten()
+            .find(|todo| todo.id == id)
+            .map(|todo| todo.title.clone())
+            .unwrap_or_default()
+    }
+
+    /// "today", "tomorrow", "backlog" or a short date.
+    fn scope_label(&self, scope: ListScope) -> String {
+        let today = self.services.today();
+
+        match scope {
+            ListScope::Backlog => "backlog".to_string(),
+            ListScope::Day(date) if date == today => "today".to_string(),
+            ListScope::Day(date) if date == today + ChronoDuration::days(1) => {
+                "tomorrow".to_string()
+            }
+            ListScope::Day(date) => date.format("%a %b %-d").to_string(),
+        }
+    }
+
+    fn moved_message(&mut self, id: Uuid, scope: ListScope) {
+        let title = self.title_of(id);
+        let target = self.scope_label(scope);
+
+        self.status.info(format!("Moved '{title}' to {target}"));
+    }
+
+    pub fn open_prompt(&mut self, kind: PromptKind) {
+        let from_backlog = matches!(self.ui_mode, UiMode::Backlog);
+
+        if kind.acts_on_todos() && self.bulk_target_ids(from_backlog).is_empty() {
+            return;
+        }
+
+        let mut input = TextInput::default();
+
+        if kind == PromptKind::Filter
+            && let Some(filter) = &self.filter
+        {
+            input.set_value(&filter.text);
+        }
+
+        if kind == PromptKind::RenameColumn
+            && let Some(title) = self.board.backlog_titles.get(self.backlog_cursor.column)
+        {
+            input.set_value(title);
+        }
+
+        self.ui_mode = UiMode::Prompt(PromptState {
+            input,
+            kind,
+            from_backlog,
+        });
+    }
+}
+
+/// "1 todo", "3 todos".
+pub(super) fn count(n: usize) -> String {
+    if n == 1 {
+        "1 todo".to_string()
+    } else {
+        format!("{n} todos")
+    }
+}
+
+/// Drop todos that aren't in `matching`, the active filter's matches.
+fn keep_matching(todos: Vec<todo::Model>, matching: Option<&HashSet<Uuid>>) -> Vec<todo::Model> {
+    match matching {
+        Some(matching) => todos
+            .into_iter()
+            .filter(|todo| matching.contains(&todo.id))
+            .collect(),
+        None => todos,
     }
 }
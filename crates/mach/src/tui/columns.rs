@@ -0,0 +1,127 @@
+//! Adding, renaming, reordering and removing backlog columns from the
+//! backlog view.
+
+use super::App;
+use super::actions::count;
+use super::cursor::Horizontal;
+use super::modes::UiMode;
+
+impl App {
+    /// Name of the focused backlog column.
+    fn focused_column_title(&self) -> String {
+        self.board
+            .backlog_titles
+            .get(self.backlog_cursor.column)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn add_backlog_column(&mut self, name: &str) -> miette::Result<()> {
+        let col = self
+            .runtime
+            .block_on(self.services.backlog.add_column(name))?;
+
+        self.refresh_backlog()?;
+
+        self.ui_mode = UiMode::Backlog;
+        self.backlog_cursor.column = col;
+        self.backlog_cursor.selection = None;
+
+        self.status
+            .info(format!("Added column '{}'", self.focused_column_title()));
+
+        Ok(())
+    }
+
+    pub fn rename_backlog_column(&mut self, name: &str) -> miette::Result<()> {
+        let col = self.backlog_cursor.column;
+        let old = self.focused_column_title();
+
+        self.runtime
+            .block_on(self.services.backlog.rename_column(col, name))?;
+
+        self.refresh_backlog()?;
+
+        self.ui_mode = UiMode::Backlog;
+
+        self.status.info(format!(
+            "Renamed column '{old}' to '{}'",
+            self.focused_column_title()
+        ));
+
+        Ok(())
+    }
+
+    /// Swap the focused column with its neighbour, keeping focus on it.
+    pub fn move_backlog_column(&mut self, dir: Horizontal) -> miette::Result<()> {
+        let col = self.backlog_cursor.column;
+
+        let to = match dir {
+            Horizontal::Left if col > 0 => col - 1,
+            Horizontal::Right if col + 1 < self.board.backlog_columns.len() => col + 1,
+            _ => return Ok(()),
+        };
+
+        let to = self
+            .runtime
+            .block_on(self.services.backlog.move_column(col, to))?;
+
+        self.refresh_backlog()?;
+
+        self.ui_mode = UiMode::Backlog;
+        self.backlog_cursor.rows.swap(col, to);
+        self.backlog_cursor.column = to;
+        self.backlog_cursor.selection = None;
+
+        Ok(())
+    }
+
+    /// Ask before removing the focused column; `D` again removes it, like
+    /// `dd` for todos.
+    pub fn confirm_remove_backlog_column(&mut self) -> miette::Result<()> {
+        if self.board.backlog_titles.len() <= 1 {
+            miette::bail!("the backlog needs at least one column");
+        }
+
+        self.pending_remove_column = true;
+
+        self.status.info(format!(
+            "Remove column '{}'? Press D to confirm",
+            self.focused_column_title()
+        ));
+
+        Ok(())
+    }
+
+    /// Remove the focused column, moving its todos next door.
+    pub fn remove_backlog_column(&mut self) -> miette::Result<()> {
+        let col = self.backlog_cursor.column;
+
+        let removed = self
+            .runtime
+            .block_on(self.services.backlog.remove_column(col))?;
+
+        self.backlog_cursor.rows.remove(col);
+        self.backlog_cursor.column = col.saturating_sub(1);
+        self.backlog_cursor.selection = None;
+
+        self.refresh_backlog()?;
+
+        self.ui_mode = UiMode::Backlog;
+
+        let message = if removed.moved == 0 {
+            format!("Removed column '{}'", removed.name)
+        } else {
+            format!(
+                "Removed column '{}'; moved {} to '{}'",
+                removed.name,
+                count(removed.moved),
+                removed.into
+            )
+        };
+
+        self.status.info(message);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,433 @@
+//! The `:` command line: parsing, tab completion, history and dispatch to
+//! the same actions the key bindings use.
+
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::dates::parse_date;
+use crate::service::settings::{self, SETTINGS};
+
+use super::App;
+use super::cursor::Horizontal;
+use super::modes::{CommandState, Completion, UiMode};
+use super::status::ReportExt;
+
+/// A parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Reschedule the marked or focused todos (`someday` for the backlog).
+    Move(String),
+    /// `+tag` / `-tag` the marked or focused todos.
+    Tag(String),
+    /// Show the week containing a date expression, or this week.
+    Week(String),
+    Today,
+    Done,
+    Delete,
+    /// Set (or with no query, clear) the filter.
+    Filter(String),
+    Backlog,
+    Board,
+    /// Store a setting from the config registry.
+    Set {
+        key: String,
+        value: String,
+    },
+    /// Go back to a setting's default.
+    Reset(String),
+    /// Change the focused backlog column.
+    Column(ColumnAction),
+    /// Write the scheduled todos to an iCalendar file.
+    Export {
+        path: String,
+    },
+    Settings,
+    Help,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnAction {
+    Add(String),
+    Rename(String),
+    Remove,
+    Left,
+    Right,
+}
+
+/// Every command with its usage, in the order completion offers them.
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("move", "move <date|someday>"),
+    ("tag", "tag +add -remove"),
+    ("week", "week [date]"),
+    ("today", "today"),
+    ("done", "done"),
+    ("delete", "delete"),
+    ("filter", "filter [query]"),
+    ("backlog", "backlog"),
+    ("board", "board"),
+    ("set", "set <setting> <value>"),
+    ("reset", "reset <setting>"),
+    (
+        "column",
+        "column add|rename <name>, column left|right|remove",
+    ),
+    ("export", "export ics [path]"),
+    ("settings", "settings"),
+    ("help", "help"),
+    ("quit", "quit"),
+];
+
+/// Where `:export ics` writes when no path is given.
+const DEFAULT_EXPORT_PATH: &str = "mach.ics";
+
+const COLUMN_ACTIONS: &[&str] = &["add", "rename", "left", "right", "remove"];
+
+const DATE_WORDS: &[&str] = &[
+    "today", "tomorrow", "mon", "tue", "wed", "thu", "fri", "sat", "sun", "next",
+];
+
+/// Short names accepted by `:set` for the most used settings.
+const SETTING_ALIASES: &[(&str, &str)] =
+    &[("weekstart", "week_start"), ("overflow", "title_overflow")];
+
+impl Command {
+    /// Parse a command line. A line that is a date rather than a command is
+    /// treated as `week <line>`, so `:fri` jumps like `gd` does.
+    pub fn parse(line: &str, today: NaiveDate) -> miette::Result<Self> {
+        let line = line.trim();
+        let (name, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        let command = match name {
+            "move" | "m" => Self::Move(required(name, rest)?),
+            "tag" => Self::Tag(required(name, rest)?),
+            "week" | "w" | "goto" => Self::Week(rest.to_string()),
+            "today" => Self::Today,
+            "done" => Self::Done,
+            "delete" | "del" => Self::Delete,
+            "filter" => Self::Filter(rest.to_string()),
+            "backlog" => Self::Backlog,
+            "board" => Self::Board,
+            "set" => {
+                let (key, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+
+                if key.is_empty() || value.trim().is_empty() {
+                    miette::bail!("usage: set <setting> <value>");
+                }
+
+                Self::Set {
+                    key: setting_key(key).to_string(),
+                    value: value.trim().to_string(),
+                }
+            }
+            "reset" => Self::Reset(setting_key(&required(name, rest)?).to_string()),
+            "column" | "col" => {
+                let rest = required("column", rest)?;
+                let (action, arg) = rest.split_once(char::is_whitespace).unwrap_or((&rest, ""));
+                let arg = arg.trim();
+
+                let action = match action {
+                    "add" if !arg.is_empty() => ColumnAction::Add(arg.to_string()),
+                    "rename" if !arg.is_empty() => ColumnAction::Rename(arg.to_string()),
+                    "remove" | "rm" => ColumnAction::Remove,
+                    "left" => ColumnAction::Left,
+                    "right" => ColumnAction::Right,
+                    _ => miette::bail!("usage: column add|rename <name>, column left|right|remove"),
+                };
+
+                Self::Column(action)
+            }
+            "export" => {
+                let rest = required(name, rest)?;
+                let (format, path) = rest.split_once(char::is_whitespace).unwrap_or((&rest, ""));
+                let path = path.trim();
+
+                if format != "ics" {
+                    miette::bail!("unsupported export format '{format}' (expected ics)");
+                }
+
+                Self::Export {
+                    path: if path.is_empty() {
+                        DEFAULT_EXPORT_PATH.to_string()
+                    } else {
+                        path.to_string()
+                    },
+                }
+            }
+            "settings" => Self::Settings,
+            "help" => Self::Help,
+            "quit" | "q" => Self::Quit,
+            _ if parse_date(line, today).is_some() => Self::Week(line.to_string()),
+            _ => miette::bail!("unknown command '{name}'"),
+        };
+
+        Ok(command)
+    }
+}
+
+fn required(name: &str, rest: &str) -> miette::Result<String> {
+    if rest.is_empty() {
+        let usage = COMMANDS
+            .iter()
+            .find(|(command, _)| *command == name)
+            .map_or(name, |(_, usage)| usage);
+
+        miette::bail!("usage: {usage}");
+    }
+
+    Ok(rest.to_string())
+}
+
+/// The registry key for `key`, resolving aliases.
+fn setting_key(key: &str) -> &str {
+    SETTING_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map_or(key, |(_, key)| key)
+}
+
+/// Completions for the word under the cursor at the end of `line`: the byte
+/// offset the word starts at and the candidates that extend it. `tags` feeds
+/// `tag` completion.
+pub fn complete(line: &str, tags: &[String]) -> (usize, Vec<String>) {
+    let start = line.rfind(char::is_whitespace).map_or(0, |idx| idx + 1);
+    let word = &line[start..];
+    let before: Vec<&str> = line[..start].split_whitespace().collect();
+
+    let options: Vec<String> = match before.as_slice() {
+        [] => COMMANDS.iter().map(|(name, _)| name.to_string()).collect(),
+        ["move" | "m"] => DATE_WORDS
+            .iter()
+            .chain(&["someday"])
+            .map(ToString::to_string)
+            .collect(),
+        ["week" | "w" | "goto"] => DATE_WORDS.iter().map(ToString::to_string).collect(),
+        ["move" | "m" | "week" | "w" | "goto", "next"] => {
+            DATE_WORDS[2..9].iter().map(ToString::to_string).collect()
+        }
+        ["tag", ..] => {
+            let sign = if word.starts_with('-') { '-' } else { '+' };
+
+            tags.iter().map(|tag| format!("{sign}{tag}")).collect()
+        }
+        ["column" | "col"] => COLUMN_ACTIONS.iter().map(ToString::to_string).collect(),
+        ["export"] => vec!["ics".to_string()],
+        ["set" | "reset"] => SETTINGS.iter().map(|def| def.key.to_string()).collect(),
+        ["set", key] => settings::lookup(setting_key(key))
+            .ok()
+            .and_then(|def| def.choices())
+            .map(|choices| choices.iter().map(ToString::to_string).collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    let candidates = options
+        .into_iter()
+        .filter(|option| option.starts_with(word) && option != word)
+        .collect();
+
+    (start, candidates)
+}
+
+impl App {
+    pub fn open_command_line(&mut self) {
+        let from_backlog = matches!(self.ui_mode, UiMode::Backlog);
+
+        self.ui_mode = UiMode::Command(CommandState::new(from_backlog));
+    }
+
+    pub fn handle_command_key(&mut self, key: KeyEvent) {
+        let UiMode::Command(ref mut state) = self.ui_mode else {
+            return;
+        };
+
+        if !matches!(key.code, KeyCode::Tab | KeyCode::BackTab) {
+            state.completion = None;
+        }
+
+        match key.code {
+            KeyCode::Esc => {
+                self.ui_mode = if state.from_backlog {
+                    UiMode::Backlog
+                } else {
+                    UiMode::Board
+                };
+            }
+            KeyCode::Backspace if state.input.is_empty() => {
+                self.ui_mode = if state.from_backlog {
+                    UiMode::Backlog
+                } else {
+                    UiMode::Board
+                };
+            }
+            KeyCode::Enter => {
+                let line = std::mem::take(&mut state.input).into_value();
+                let from_backlog = state.from_backlog;
+
+                self.ui_mode = if from_backlog {
+                    UiMode::Backlog
+                } else {
+                    UiMode::Board
+                };
+
+                let line = line.trim();
+
+                if line.is_empty() {
+                    return;
+                }
+
+                if self.command_history.last().map(String::as_str) != Some(line) {
+                    self.command_history.push(line.to_string());
+                }
+
+                self.run_command(line, from_backlog)
+                    .report(&mut self.status);
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                let tags = self.board_tags();
+
+                let UiMode::Command(ref mut state) = self.ui_mode else {
+                    return;
+                };
+
+                let completion = match state.completion.take() {
+                    Some(mut completion) => {
+                        let len = completion.candidates.len();
+
+                        completion.index = if key.code == KeyCode::Tab {
+                            (completion.index + 1) % len
+                        } else {
+                            (completion.index + len - 1) % len
+                        };
+
+                        completion
+                    }
+                    None => {
+                        let line = state.input.value().to_string();
+                        let (start, candidates) = complete(&line, &tags);
+
+                        if candidates.is_empty() {
+                            return;
+                        }
+
+                        Completion {
+                            prefix: line[..start].to_string(),
+                            candidates,
+                            index: 0,
+                        }
+                    }
+                };
+
+                let mut line = format!(
+                    "{}{}",
+                    completion.prefix, completion.candidates[completion.index]
+                );
+
+                // A unique match is final: move on to the next argument.
+                if completion.candidates.len() == 1 {
+                    line.push(' ');
+                } else {
+                    state.completion = Some(completion);
+                }
+
+                state.input.set_value(line);
+            }
+            KeyCode::Up => {
+                if self.command_history.is_empty() {
+                    return;
+                }
+
+                let index = match state.history_index {
+                    Some(idx) => idx.saturating_sub(1),
+                    None => {
+                        state.draft = state.input.value().to_string();
+
+                        self.command_history.len() - 1
+                    }
+                };
+
+                state.history_index = Some(index);
+                state.input.set_value(self.command_history[index].clone());
+            }
+            KeyCode::Down => {
+                let Some(idx) = state.history_index else {
+                    return;
+                };
+
+                if idx + 1 < self.command_history.len() {
+                    state.history_index = Some(idx + 1);
+                    state.input.set_value(self.command_history[idx + 1].clone());
+                } else {
+                    state.history_index = None;
+                    state.input.set_value(std::mem::take(&mut state.draft));
+                }
+            }
+            _ => {
+                state.input.handle_key(key);
+            }
+        }
+    }
+
+    /// Run a command line against the view it was opened from.
+    pub fn run_command(&mut self, line: &str, from_backlog: bool) -> miette::Result<()> {
+        match Command::parse(line, self.services.today())? {
+            Command::Move(target) => self.bulk_reschedule(from_backlog, &target)?,
+            Command::Tag(tags) => self.bulk_tag(from_backlog, &tags)?,
+            Command::Week(date) => {
+                self.ui_mode = UiMode::Board;
+
+                if date.is_empty() {
+                    self.jump_to_date(self.services.today())?;
+                } else {
+                    self.jump_to_input(&date)?;
+                }
+            }
+            Command::Today => {
+                self.ui_mode = UiMode::Board;
+
+                self.jump_to_date(self.services.today())?;
+            }
+            Command::Done => self.bulk_complete(from_backlog)?,
+            Command::Delete => self.bulk_delete(from_backlog)?,
+            Command::Filter(query) => self.set_filter(&query)?,
+            Command::Backlog => self.open_backlog(),
+            Command::Board => self.ui_mode = UiMode::Board,
+            Command::Set { key, value } => self.set_setting(&key, &value)?,
+            Command::Reset(key) => self.reset_setting(&key)?,
+            Command::Column(action) => match action {
+                ColumnAction::Add(name) => self.add_backlog_column(&name)?,
+                ColumnAction::Rename(name) => self.rename_backlog_column(&name)?,
+                ColumnAction::Remove if !from_backlog => {
+                    miette::bail!("column remove only works in the backlog view")
+                }
+                ColumnAction::Remove => self.confirm_remove_backlog_column()?,
+                ColumnAction::Left => self.move_backlog_column(Horizontal::Left)?,
+                ColumnAction::Right => self.move_backlog_column(Horizontal::Right)?,
+            },
+            Command::Export { path } => self.export_ics(&path)?,
+            Command::Settings => self.open_settings(),
+            Command::Help => self.show_help = true,
+            Command::Quit => self.should_quit = true,
+        }
+
+        Ok(())
+    }
+
+    /// Tags on the todos currently loaded, for completing `:tag`.
+    fn board_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .board
+            .days
+            .iter()
+            .chain(&self.board.backlog_columns)
+            .flatten()
+            .flat_map(|todo| todo.tags.iter().cloned())
+            .collect();
+
+        tags.sort();
+        tags.dedup();
+
+        tags
+    }
+}
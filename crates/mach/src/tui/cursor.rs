@@ -1,8 +1,10 @@
+use std::collections::HashSet;
+
 use ratatui::style::{Modifier, Style};
 use uuid::Uuid;
 
-use super::palette;
-use super::state::{BACKLOG_COLUMNS, BoardData};
+use super::palette::Palette;
+use super::state::BoardData;
 
 #[derive(Clone, Copy)]
 pub enum Horizontal {
@@ -23,6 +25,59 @@ pub struct Selection {
     pub row: Option<usize>,
 }
 
+/// Todos marked for a bulk operation, plus the anchor used for range marks.
+#[derive(Default)]
+pub struct Marks {
+    ids: HashSet<Uuid>,
+    anchor: Option<(usize, usize)>,
+}
+
+impl Marks {
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: Uuid) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Flip the mark on `id` and make its position the anchor for range marks.
+    pub fn toggle(&mut self, id: Uuid, column: usize, row: usize) {
+        if !self.ids.remove(&id) {
+            self.ids.insert(id);
+        }
+
+        self.anchor = Some((column, row));
+    }
+
+    /// Mark every id in `ids`, anchoring at the given position.
+    pub fn extend(&mut self, ids: impl IntoIterator<Item = Uuid>, column: usize, row: usize) {
+        self.ids.extend(ids);
+        self.anchor = Some((column, row));
+    }
+
+    /// Anchor row when it lies in `column`.
+    pub fn anchor_row(&self, column: usize) -> Option<usize> {
+        self.anchor
+            .filter(|(col, _)| *col == column)
+            .map(|(_, row)| row)
+    }
+
+    pub fn clear(&mut self) {
+        self.ids.clear();
+        self.anchor = None;
+    }
+
+    /// Drop marks for todos that are no longer visible.
+    pub fn retain(&mut self, visible: impl Fn(Uuid) -> bool) {
+        self.ids.retain(|id| visible(*id));
+
+        if self.ids.is_empty() {
+            self.anchor = None;
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct BacklogSelection {
     pub id: Uuid,
@@ -34,6 +89,7 @@ pub struct CursorState {
     pub focus: usize,
     pub day_rows: Vec<usize>,
     pub selection: Option<Selection>,
+    pub marks: Marks,
 }
 
 impl CursorState {
@@ -42,6 +98,7 @@ impl CursorState {
             focus: 0,
             day_rows: vec![0; num_days],
             selection: None,
+            marks: Marks::default(),
         }
     }
 
@@ -80,24 +137,28 @@ impl CursorState {
         self.day_rows.get(col).copied().filter(|r| *r < len)
     }
 
-    pub fn line_style(&self, col: usize, row: usize, board: &BoardData) -> Style {
+    pub fn line_style(
+        &self,
+        col: usize,
+        row: usize,
+        board: &BoardData,
+        palette: &Palette,
+    ) -> Style {
         if let Some(selection) = self.selection
             && selection.column == col
             && selection.row == Some(row)
         {
-            return Style::default()
-                .fg(palette::ACCENT)
-                .add_modifier(Modifier::BOLD);
+            return palette.accent.add_modifier(Modifier::BOLD);
         }
 
         if self.focus == col
             && let Some(current_row) = self.row_for(col, board)
             && current_row == row
         {
-            return Style::default().fg(palette::ACTIVE);
+            return palette.active;
         }
 
-        Style::default().fg(palette::TEXT)
+        palette.text
     }
 
     pub fn is_selected(&self, id: Uuid) -> bool {
@@ -127,6 +188,9 @@ impl CursorState {
             }
         }
 
+        self.marks
+            .retain(|id| board.find_day_position(id).is_some());
+
         if let Some(selection) = self.selection {
             if let Some((col, row)) = board.find_day_position(selection.id) {
                 self.selection = Some(Selection {
@@ -155,20 +219,22 @@ impl CursorState {
 
 pub struct BacklogCursor {
     pub column: usize,
-    pub rows: [usize; BACKLOG_COLUMNS],
+    pub rows: Vec<usize>,
     pub selection: Option<BacklogSelection>,
+    pub marks: Marks,
 }
 
 impl BacklogCursor {
     pub fn new() -> Self {
         Self {
             column: 0,
-            rows: [0; BACKLOG_COLUMNS],
+            rows: Vec::new(),
             selection: None,
+            marks: Marks::default(),
         }
     }
 
-    pub fn move_horizontal(&mut self, dir: Horizontal) {
+    pub fn move_horizontal(&mut self, dir: Horizontal, board: &BoardData) {
         match dir {
             Horizontal::Left => {
                 if self.column > 0 {
@@ -176,7 +242,7 @@ impl BacklogCursor {
                 }
             }
             Horizontal::Right => {
-                if self.column + 1 < BACKLOG_COLUMNS {
+                if self.column + 1 < board.backlog_columns.len() {
                     self.column += 1;
                 }
             }
@@ -217,29 +283,33 @@ impl BacklogCursor {
             return None;
         }
 
-        let row = self.rows[col];
+        let row = self.rows.get(col).copied()?;
 
         if row < len { Some(row) } else { None }
     }
 
-    pub fn line_style(&self, col: usize, row: usize, board: &BoardData) -> Style {
+    pub fn line_style(
+        &self,
+        col: usize,
+        row: usize,
+        board: &BoardData,
+        palette: &Palette,
+    ) -> Style {
         if let Some(selection) = self.selection
             && selection.column == col
             && selection.row == Some(row)
         {
-            return Style::default()
-                .fg(palette::ACCENT)
-                .add_modifier(Modifier::BOLD);
+            return palette.accent.add_modifier(Modifier::BOLD);
         }
 
         if self.column == col
             && let Some(current_row) = self.row_for(col, board)
             && current_row == row
         {
-            return Style::default().fg(palette::ACTIVE);
+            return palette.active;
         }
 
-        Style::default().fg(palette::TEXT)
+        palette.text
     }
 
     pub fn is_selected(&self, id: Uuid) -> bool {
@@ -253,7 +323,12 @@ impl BacklogCursor {
     }
 
     pub fn sync_after_refresh(&mut self, board: &BoardData) {
-        for col in 0..BACKLOG_COLUMNS {
+        let columns = board.backlog_columns.len();
+
+        self.rows.resize(columns, 0);
+        self.column = self.column.min(columns.saturating_sub(1));
+
+        for col in 0..columns {
             let len = board.backlog_col_len(col);
             if len == 0 {
                 self.rows[col] = 0;
@@ -262,6 +337,9 @@ impl BacklogCursor {
             }
         }
 
+        self.marks
+            .retain(|id| board.find_backlog_position(id).is_some());
+
         if let Some(selection) = self.selection {
             if let Some((col, row)) = board.find_backlog_position(selection.id) {
                 self.selection = Some(BacklogSelection {
@@ -2,20 +2,29 @@ use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 use uuid::Uuid;
 
-use crate::service::config::WeekStart;
+use crate::service::config::TitleOverflow;
 
 use super::App;
-use super::modes::{AddTodoState, DetailField, DetailState, SettingsState, UiMode};
-use super::palette;
-use super::state::{BACKLOG_COLUMNS, TodoView};
+use super::command::COMMANDS;
+use super::cursor::Marks;
+use super::markdown;
+use super::modes::{
+    AddTarget, AddTodoState, CommandState, DetailField, DetailState, PromptState, RolloverState,
+    SettingsState, UiMode,
+};
+use super::mouse::{ColumnHit, HitColumn};
+use super::state::TodoView;
+use super::status::MessageKind;
 
 impl App {
     pub fn draw(&mut self, frame: &mut Frame<'_>) {
+        self.hits.borrow_mut().clear();
+
         match &self.ui_mode {
             UiMode::Board => self.draw_board(frame),
             UiMode::Backlog => self.draw_backlog_view(frame),
@@ -28,8 +37,8 @@ impl App {
             }
             UiMode::AddTodo(state) => {
                 match state.target {
-                    super::modes::AddTarget::Day(_) => self.draw_board(frame),
-                    super::modes::AddTarget::BacklogColumn(_) => self.draw_backlog_view(frame),
+                    AddTarget::Day(_) => self.draw_board(frame),
+                    AddTarget::BacklogColumn(_) => self.draw_backlog_view(frame),
                 }
                 let state = state.clone();
                 self.draw_add_todo(frame, &state);
@@ -45,6 +54,35 @@ impl App {
 
                 self.draw_detail(frame, &state);
             }
+            UiMode::Prompt(state) => {
+                if state.from_backlog {
+                    self.draw_backlog_view(frame);
+                } else {
+                    self.draw_board(frame);
+                }
+
+                let state = state.clone();
+
+                self.draw_prompt(frame, &state);
+            }
+            UiMode::Command(state) => {
+                if state.from_backlog {
+                    self.draw_backlog_view(frame);
+                } else {
+                    self.draw_board(frame);
+                }
+
+                let state = state.clone();
+
+                self.draw_command_line(frame, &state);
+            }
+            UiMode::Rollover(state) => {
+                self.draw_board(frame);
+
+                let state = state.clone();
+
+                self.draw_rollover(frame, &state);
+            }
         }
 
         if self.show_help {
@@ -64,10 +102,12 @@ impl App {
             constraints.push(Constraint::Fill(1));
         }
 
+        let (board_area, footer_area) = self.split_footer(frame.area());
+
         let areas = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(constraints)
-            .split(frame.area());
+            .split(board_area);
 
         let focused = self.cursor.focus;
         let mut col_idx = 0;
@@ -82,9 +122,9 @@ impl App {
                 let adjacent_to_focus = sep_idx == focused || sep_idx + 1 == focused;
 
                 let style = if adjacent_to_focus {
-                    Style::default().fg(palette::FOCUS)
+                    self.palette.focus
                 } else {
-                    Style::default().fg(palette::BORDER)
+                    self.palette.border
                 };
 
                 let lines: Vec<Line<'_>> = (0..area.height).map(|_| Line::from("│")).collect();
@@ -93,20 +133,44 @@ impl App {
                 frame.render_widget(separator, area);
             }
         }
+
+        if let Some(footer_area) = footer_area {
+            let width = areas.get(focused * 2).map(|a| a.width).unwrap_or(0);
+
+            let todo = self
+                .cursor
+                .row_for(focused, &self.board)
+                .and_then(|row| self.board.days.get(focused)?.get(row))
+                .filter(|todo| {
+                    let selected = self.cursor.is_selected(todo.id);
+                    let marked = self.cursor.marks.contains(todo.id);
+
+                    self.is_cut_off(todo, selected, marked, width)
+                });
+
+            let first = self.state.week_start;
+            let last = self.state.columns.last().map_or(first, |col| col.date);
+            let range = format!("{} – {}", first.format("%b %-d"), last.format("%b %-d, %Y"));
+
+            self.draw_status_bar(frame, todo, range, footer_area);
+        }
     }
 
     pub fn draw_backlog_view(&self, frame: &mut Frame<'_>) {
         let outer = Block::default()
             .title("Someday / Backlog")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(palette::FOCUS));
+            .border_style(self.palette.focus);
 
-        let inner = outer.inner(frame.area());
-        frame.render_widget(outer, frame.area());
+        let (backlog_area, footer_area) = self.split_footer(frame.area());
 
-        let mut constraints = Vec::with_capacity(BACKLOG_COLUMNS * 2 - 1);
+        let inner = outer.inner(backlog_area);
+        frame.render_widget(outer, backlog_area);
 
-        for i in 0..BACKLOG_COLUMNS {
+        let num_columns = self.board.backlog_columns.len();
+        let mut constraints = Vec::with_capacity(num_columns * 2);
+
+        for i in 0..num_columns {
             if i > 0 {
                 constraints.push(Constraint::Length(1));
             }
@@ -132,9 +196,9 @@ impl App {
                 let adjacent_to_focus = sep_idx == focused || sep_idx + 1 == focused;
 
                 let style = if adjacent_to_focus {
-                    Style::default().fg(palette::FOCUS)
+                    self.palette.focus
                 } else {
-                    Style::default().fg(palette::BORDER)
+                    self.palette.border
                 };
 
                 let lines: Vec<Line<'_>> = (0..area.height).map(|_| Line::from("│")).collect();
@@ -143,27 +207,117 @@ impl App {
                 frame.render_widget(separator, area);
             }
         }
+
+        if let Some(footer_area) = footer_area {
+            let width = areas.get(focused * 2).map(|a| a.width).unwrap_or(0);
+
+            let todo = self
+                .backlog_cursor
+                .row_for(focused, &self.board)
+                .and_then(|row| self.board.backlog_columns.get(focused)?.get(row))
+                .filter(|todo| {
+                    let selected = self.backlog_cursor.is_selected(todo.id);
+                    let marked = self.backlog_cursor.marks.contains(todo.id);
+
+                    self.is_cut_off(todo, selected, marked, width)
+                });
+
+            let total: usize = self.board.backlog_columns.iter().map(Vec::len).sum();
+            let count = if total == 1 {
+                "1 todo".to_string()
+            } else {
+                format!("{total} todos")
+            };
+
+            self.draw_status_bar(frame, todo, count, footer_area);
+        }
+    }
+
+    /// Reserve a one-line status bar below the main view.
+    fn split_footer(&self, area: Rect) -> (Rect, Option<Rect>) {
+        if !self.status_bar || area.height < 2 {
+            return (area, None);
+        }
+
+        let main = Rect {
+            height: area.height - 1,
+            ..area
+        };
+
+        let footer = Rect {
+            y: area.y + area.height - 1,
+            height: 1,
+            ..area
+        };
+
+        (main, Some(footer))
     }
 
     fn draw_backlog_column(&self, frame: &mut Frame<'_>, col_idx: usize, area: Rect) {
         let focused = self.backlog_cursor.column == col_idx;
         let items = &self.board.backlog_columns[col_idx];
 
+        let title_style = if focused {
+            self.palette.focus.add_modifier(Modifier::BOLD)
+        } else {
+            self.palette.text
+        };
+
+        let title = self.board.backlog_titles[col_idx].as_str();
+        let underline = "─".repeat(area.width as usize);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(title).style(title_style)).centered(),
+            Rect { height: 1, ..area },
+        );
+
+        frame.render_widget(
+            Paragraph::new(Line::from(underline).style(title_style)),
+            Rect {
+                y: area.y + 1,
+                height: 1,
+                ..area
+            },
+        );
+
+        let area = Rect {
+            y: area.y + 2,
+            height: area.height.saturating_sub(2),
+            ..area
+        };
+
         let highlight_row = if focused {
             self.backlog_cursor.row_for(col_idx, &self.board)
         } else {
             None
         };
 
-        let lines = self.build_todo_lines_with_separators(
+        let (lines, spans) = self.build_todo_lines_with_separators(
             items,
             area.width,
             highlight_row,
-            |row| self.backlog_cursor.line_style(col_idx, row, &self.board),
+            |row| {
+                self.backlog_cursor
+                    .line_style(col_idx, row, &self.board, &self.palette)
+            },
             |id| self.backlog_cursor.is_selected(id),
+            &self.backlog_cursor.marks,
         );
 
-        let para = Paragraph::new(lines);
+        let scroll = scroll_offset(
+            highlight_row.and_then(|row| spans.get(row).copied()),
+            area.height,
+        );
+
+        self.hits.borrow_mut().push(ColumnHit {
+            target: HitColumn::Backlog(col_idx),
+            area,
+            body_y: area.y,
+            scroll,
+            spans,
+        });
+
+        let para = Paragraph::new(lines).scroll((scroll, 0));
 
         frame.render_widget(para, area);
     }
@@ -173,11 +327,9 @@ impl App {
         let focused = self.cursor.focus == idx;
 
         let title_style = if focused {
-            Style::default()
-                .fg(palette::FOCUS)
-                .add_modifier(Modifier::BOLD)
+            self.palette.focus.add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(palette::TEXT)
+            self.palette.text
         };
 
         let title_line = Line::from(column.title.clone()).style(title_style);
@@ -204,14 +356,28 @@ impl App {
             None
         };
 
-        let lines = self.build_todo_lines_with_separators(
+        let (lines, spans) = self.build_todo_lines_with_separators(
             items,
             area.width,
             highlight_row,
-            |row| self.cursor.line_style(idx, row, &self.board),
+            |row| self.cursor.line_style(idx, row, &self.board, &self.palette),
             |id| self.cursor.is_selected(id),
+            &self.cursor.marks,
         );
 
+        let scroll = scroll_offset(
+            highlight_row.and_then(|row| spans.get(row).copied()),
+            content_area.height,
+        );
+
+        self.hits.borrow_mut().push(ColumnHit {
+            target: HitColumn::Day(idx),
+            area,
+            body_y: content_area.y,
+            scroll,
+            spans,
+        });
+
         frame.render_widget(
             Paragraph::new(title_line).centered(),
             Rect { height: 1, ..area },
@@ -226,11 +392,14 @@ impl App {
             },
         );
 
-        let body = Paragraph::new(lines);
+        let body = Paragraph::new(lines).scroll((scroll, 0));
 
         frame.render_widget(body, content_area);
     }
 
+    /// Build the lines for a column, returning them alongside the range of
+    /// lines each todo occupies so callers can keep the cursor in view and map
+    /// mouse positions back to rows.
     fn build_todo_lines_with_separators<'a, F, S>(
         &self,
         items: &'a [TodoView],
@@ -238,7 +407,8 @@ impl App {
         highlight_row: Option<usize>,
         style_fn: F,
         is_selected_fn: S,
-    ) -> Vec<Line<'a>>
+        marks: &Marks,
+    ) -> (Vec<Line<'a>>, Vec<(usize, usize)>)
     where
         F: Fn(usize) -> Style,
         S: Fn(Uuid) -> bool,
@@ -246,110 +416,400 @@ impl App {
         let separator = "-".repeat(width as usize);
 
         let mut lines = Vec::with_capacity(items.len() * 2);
+        let mut spans = Vec::with_capacity(items.len());
 
         for (i, item) in items.iter().enumerate() {
             if i > 0 {
                 let adjacent_to_focus = highlight_row == Some(i - 1) || highlight_row == Some(i);
 
                 let sep_style = if adjacent_to_focus {
-                    Style::default().fg(palette::ACTIVE)
+                    self.palette.active
                 } else {
-                    Style::default().fg(palette::BORDER)
+                    self.palette.border
                 };
 
                 lines.push(Line::from(separator.clone()).style(sep_style));
             }
 
             let is_selected = is_selected_fn(item.id);
+            let is_marked = marks.contains(item.id);
+            let start = lines.len();
+
+            let item_lines = item.to_lines(
+                is_selected,
+                is_marked,
+                width,
+                self.overflow_pref,
+                self.services.stale_after(),
+                &self.palette,
+            );
+
+            for mut line in item_lines {
+                if is_selected {
+                    line.style = line
+                        .style
+                        .patch(self.palette.accent.add_modifier(Modifier::BOLD));
+                } else if is_marked && highlight_row != Some(i) {
+                    line.style = line.style.patch(self.palette.accent);
+                } else if highlight_row == Some(i) {
+                    line.style = line.style.patch(style_fn(i));
+                } else if item.is_stale(self.services.stale_after()) {
+                    line.style = line.style.patch(self.palette.stale);
+                }
 
-            let mut line = item.to_line_with_prefix(is_selected);
-
-            if is_selected {
-                line.style = line.style.patch(
-                    Style::default()
-                        .fg(palette::ACCENT)
-                        .add_modifier(Modifier::BOLD),
-                );
-            } else if highlight_row == Some(i) {
-                line.style = line.style.patch(style_fn(i));
+                lines.push(line);
             }
 
-            lines.push(line);
+            spans.push((start, lines.len()));
+        }
+
+        (lines, spans)
+    }
+
+    /// Whether the focused todo is truncated in a column `width` wide, so
+    /// the status bar should show its full title.
+    fn is_cut_off(&self, todo: &TodoView, selected: bool, marked: bool, width: u16) -> bool {
+        self.overflow_pref == TitleOverflow::Truncate
+            && todo.is_truncated(selected, marked, width, self.services.stale_after())
+    }
+
+    /// Status bar: the filter and the latest message (or the full title of a
+    /// truncated todo) on the left, pending keys and `context` on the right.
+    /// `truncated` is the focused todo when its title is cut off.
+    fn draw_status_bar(
+        &self,
+        frame: &mut Frame<'_>,
+        truncated: Option<&TodoView>,
+        context: String,
+        area: Rect,
+    ) {
+        let mut left = Vec::new();
+
+        if let Some(filter) = &self.filter {
+            left.push(Span::styled(
+                format!("/{}  ", filter.text),
+                self.palette.accent,
+            ));
+        }
+
+        if let Some(message) = self.status.current() {
+            let style = match message.kind {
+                MessageKind::Info => self.palette.text,
+                MessageKind::Error => self.palette.error,
+            };
+
+            left.push(Span::styled(message.text.clone(), style));
+        } else if let Some(todo) = truncated {
+            left.push(Span::raw(todo.title.clone()));
         }
 
-        lines
+        let mut right = Vec::new();
+
+        if self.pending_g {
+            right.push(Span::styled("g…  ", self.palette.active));
+        } else if self.pending_delete {
+            right.push(Span::styled("d…  ", self.palette.active));
+        } else if self.pending_remove_column {
+            right.push(Span::styled("D…  ", self.palette.active));
+        }
+
+        right.push(Span::raw(context));
+
+        let right = Line::from(right);
+        let right_width = (right.width() as u16).min(area.width);
+
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(right_width)]).areas(area);
+
+        let style = self.palette.text_dim;
+
+        frame.render_widget(Paragraph::new(Line::from(left)).style(style), left_area);
+        frame.render_widget(Paragraph::new(right).style(style), right_area);
     }
 
     pub fn draw_settings(&self, frame: &mut Frame<'_>, settings: &SettingsState) {
-        let area = centered_rect(30, 18, frame.area());
+        let area = centered_rect(60, 70, frame.area());
 
         let block = Block::default()
             .title("Settings")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(palette::FOCUS));
-
-        let (monday_style, sunday_style) = match settings.week_start {
-            WeekStart::Monday => (
-                Style::default().fg(palette::ACTIVE),
-                Style::default().fg(palette::TEXT_DIM),
-            ),
-            WeekStart::Sunday => (
-                Style::default().fg(palette::TEXT_DIM),
-                Style::default().fg(palette::ACTIVE),
-            ),
+            .border_style(self.palette.focus);
+
+        let inner = block.inner(area);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [list_area, _, description_area, hint_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let key_width = settings
+            .entries
+            .iter()
+            .map(|entry| entry.def.key.len())
+            .max()
+            .unwrap_or(0);
+
+        let lines: Vec<Line<'_>> = settings
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let focused = idx == settings.cursor;
+                let marker = if focused { "› " } else { "  " };
+                let key = format!("{marker}{:<key_width$}  ", entry.def.key);
+
+                let key_style = if focused {
+                    self.palette.active
+                } else {
+                    self.palette.text
+                };
+
+                let mut spans = vec![Span::styled(key, key_style)];
+
+                match &settings.editing {
+                    Some(input) if focused => {
+                        spans.extend(input.render(self.palette.active).remove(0).spans);
+                    }
+                    _ => {
+                        let value_style = if entry.is_set {
+                            self.palette.text
+                        } else {
+                            self.palette.text_dim
+                        };
+
+                        spans.push(Span::styled(entry.display(), value_style));
+                    }
+                }
+
+                Line::from(spans)
+            })
+            .collect();
+
+        let offset = scroll_offset(
+            Some((settings.cursor, settings.cursor + 1)),
+            list_area.height,
+        );
+
+        frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), list_area);
+
+        let description =
+            Line::from(settings.current().def.description).style(self.palette.text_dim);
+
+        frame.render_widget(Paragraph::new(description), description_area);
+
+        let hint = if settings.editing.is_some() {
+            "[Enter] save  [Esc] cancel"
+        } else {
+            "[j/k] move  [Enter/h/l] change  [r] reset  [Esc] close"
         };
 
+        frame.render_widget(
+            Paragraph::new(Line::from(hint).style(self.palette.text_dim)),
+            hint_area,
+        );
+    }
+
+    pub fn draw_add_todo(&self, frame: &mut Frame<'_>, state: &AddTodoState) {
+        let area = centered_rect(35, 15, frame.area());
+
+        let block = Block::default()
+            .title("Add Todo")
+            .borders(Borders::ALL)
+            .border_style(self.palette.focus);
+
+        let inner = block.inner(area);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let style = self.palette.active;
+        let mut input_line = Line::from(Span::styled("› ", style));
+
+        input_line
+            .spans
+            .extend(state.input.render(style).remove(0).spans);
+
         let lines = vec![
-            Line::from("Week Start"),
+            input_line,
             Line::from(""),
-            Line::from(vec![
-                "[m] ".into(),
-                ratatui::text::Span::styled("Monday", monday_style),
-            ]),
-            Line::from(vec![
-                "[s] ".into(),
-                ratatui::text::Span::styled("Sunday", sunday_style),
-            ]),
-            Line::from(""),
-            Line::from("[Esc] close").style(Style::default().fg(palette::TEXT_DIM)),
+            Line::from("[Enter] add  [↑/↓] history  [Esc] cancel").style(self.palette.text_dim),
         ];
 
-        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    pub fn draw_rollover(&self, frame: &mut Frame<'_>, state: &RolloverState) {
+        let area = centered_rect(50, 40, frame.area());
+
+        let block = Block::default()
+            .title(format!("Rolled over to today ({})", state.items.len()))
+            .borders(Borders::ALL)
+            .border_style(self.palette.focus);
+
+        let inner = block.inner(area);
 
         frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
 
-        frame.render_widget(paragraph, area);
+        let mut lines: Vec<Line<'_>> = state
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let style = if idx == state.cursor {
+                    self.palette.active
+                } else if item.is_stale(self.services.stale_after()) {
+                    self.palette.stale
+                } else {
+                    self.palette.text
+                };
+
+                let times = match item.rollover_count {
+                    1 => "once".to_string(),
+                    n => format!("{n} times"),
+                };
+
+                Line::from(vec![
+                    Span::styled(item.title.clone(), style),
+                    Span::styled(format!("  ↻ {times}"), self.palette.text_dim),
+                ])
+            })
+            .collect();
+
+        lines.push(Line::from(""));
+
+        if let Some(input) = &state.reschedule {
+            let style = self.palette.active;
+            let mut input_line = Line::from(Span::styled("Reschedule to › ", style));
+
+            input_line.spans.extend(input.render(style).remove(0).spans);
+
+            lines.push(input_line);
+        } else {
+            lines.push(
+                Line::from("[Enter] keep [s] backlog [d] delete [r] reschedule [Esc] all")
+                    .style(self.palette.text_dim),
+            );
+        }
+
+        frame.render_widget(Paragraph::new(lines), inner);
     }
 
-    pub fn draw_add_todo(&self, frame: &mut Frame<'_>, state: &AddTodoState) {
+    pub fn draw_prompt(&self, frame: &mut Frame<'_>, state: &PromptState) {
         let area = centered_rect(35, 15, frame.area());
 
+        let title = if !state.kind.acts_on_todos() {
+            state.kind.title().to_string()
+        } else {
+            let count = self.bulk_target_ids(state.from_backlog).len();
+
+            format!("{} ({count})", state.kind.title())
+        };
+
         let block = Block::default()
-            .title("Add Todo")
+            .title(title)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(palette::FOCUS));
+            .border_style(self.palette.focus);
 
         let inner = block.inner(area);
 
         frame.render_widget(Clear, area);
         frame.render_widget(block, area);
 
+        let style = self.palette.active;
+        let mut input_line = Line::from(Span::styled("› ", style));
+
+        input_line
+            .spans
+            .extend(state.input.render(style).remove(0).spans);
+
         let lines = vec![
-            Line::from(format!("› {}_", state.input)).style(Style::default().fg(palette::ACTIVE)),
+            input_line,
             Line::from(""),
-            Line::from("[Enter] add  [Esc] cancel").style(Style::default().fg(palette::TEXT_DIM)),
+            Line::from("[Enter] apply  [Esc] cancel").style(self.palette.text_dim),
         ];
 
         frame.render_widget(Paragraph::new(lines), inner);
     }
 
+    /// The `:` line over the status bar, with the usage of the command being
+    /// typed and a row of completion candidates above it while cycling.
+    pub fn draw_command_line(&self, frame: &mut Frame<'_>, state: &CommandState) {
+        let area = frame.area();
+
+        if area.height < 2 {
+            return;
+        }
+
+        let line_area = Rect {
+            y: area.y + area.height - 1,
+            height: 1,
+            ..area
+        };
+
+        let style = self.palette.active;
+        let mut input_line = Line::from(Span::styled(":", style));
+
+        input_line
+            .spans
+            .extend(state.input.render(style).remove(0).spans);
+
+        let name = state.input.value().split_whitespace().next().unwrap_or("");
+        let usage = COMMANDS
+            .iter()
+            .find(|(command, _)| *command == name)
+            .map_or("", |(_, usage)| usage);
+
+        let usage = Line::from(usage).style(self.palette.text_dim);
+        let usage_width = (usage.width() as u16).min(area.width);
+
+        let [input_area, usage_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(usage_width)])
+                .areas(line_area);
+
+        frame.render_widget(Clear, line_area);
+        frame.render_widget(Paragraph::new(input_line), input_area);
+        frame.render_widget(Paragraph::new(usage), usage_area);
+
+        let Some(completion) = &state.completion else {
+            return;
+        };
+
+        let menu_area = Rect {
+            y: line_area.y - 1,
+            ..line_area
+        };
+
+        let mut spans = Vec::new();
+
+        for (idx, candidate) in completion.candidates.iter().enumerate() {
+            let style = if idx == completion.index {
+                self.palette.accent.add_modifier(Modifier::BOLD)
+            } else {
+                self.palette.text
+            };
+
+            spans.push(Span::styled(candidate.clone(), style));
+            spans.push(Span::raw("  "));
+        }
+
+        frame.render_widget(Clear, menu_area);
+        frame.render_widget(Paragraph::new(Line::from(spans)), menu_area);
+    }
+
     pub fn draw_detail(&self, frame: &mut Frame<'_>, state: &DetailState) {
         let area = centered_rect(70, 50, frame.area());
 
         let block = Block::default()
             .title("Todo")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(palette::FOCUS));
+            .border_style(self.palette.focus);
 
         let inner = block.inner(area);
 
@@ -365,64 +825,84 @@ impl App {
 
         let mut lines: Vec<Line<'_>> = Vec::new();
 
+        // Rows left for notes once the other fields and the hint lines are drawn.
+        let notes_rows = (inner.height as usize).saturating_sub(8).max(1);
+
         for field in fields {
             let is_focused = state.field == field;
-            let is_editing = is_focused && state.editing.is_some();
+            let editing = state.editing.as_ref().filter(|_| is_focused);
 
             let label = field.label();
 
-            let value = if is_editing {
-                state.editing.as_ref().unwrap().clone()
-            } else {
-                state.field_value(field)
-            };
-
             let style = if is_focused {
-                Style::default().fg(palette::ACTIVE)
+                self.palette.active
             } else {
-                Style::default().fg(palette::TEXT)
+                self.palette.text
             };
 
             if field == DetailField::Notes {
                 lines.push(Line::from(""));
 
-                let prefix = if is_editing { "› " } else { "  " };
+                let prefix = if editing.is_some() { "› " } else { "  " };
 
                 lines.push(Line::from(format!("{prefix}{label}:")).style(style));
 
-                if is_editing {
-                    let note_lines: Vec<&str> = value.split('\n').collect();
+                if let Some(input) = editing {
+                    let (cursor_line, _) = input.cursor_position();
+                    let start = (cursor_line + 1).saturating_sub(notes_rows);
+
+                    for line in input.render(style).into_iter().skip(start).take(notes_rows) {
+                        let mut indented = Line::from("    ");
+
+                        indented.spans.extend(line.spans);
 
-                    for (i, line) in note_lines.iter().enumerate() {
-                        let cursor = if i == note_lines.len() - 1 { "_" } else { "" };
-                        lines.push(Line::from(format!("    {line}{cursor}")).style(style));
+                        lines.push(indented);
                     }
-                } else if value.is_empty() {
-                    lines.push(
-                        Line::from("    (empty)").style(Style::default().fg(palette::TEXT_DIM)),
-                    );
-                } else {
-                    for line in value.lines() {
+                } else if state.notes.is_empty() {
+                    lines.push(Line::from("    (empty)").style(self.palette.text_dim));
+                } else if state.raw_notes {
+                    for line in state.notes.lines() {
                         lines.push(Line::from(format!("    {line}")).style(style));
                     }
+                } else {
+                    let text_style = self.palette.text;
+
+                    for line in
+                        markdown::render(&state.notes, text_style, state.task_cursor, &self.palette)
+                    {
+                        let mut indented = Line::from("    ");
+
+                        indented.spans.extend(line.spans);
+
+                        lines.push(indented);
+                    }
                 }
+            } else if let Some(input) = editing {
+                let mut line = Line::from(Span::styled(format!("› {label}: "), style));
+
+                line.spans.extend(input.render(style).remove(0).spans);
+
+                lines.push(line);
             } else {
                 let prefix = if is_focused { "› " } else { "  " };
-                let suffix = if is_editing { "_" } else { "" };
+                let value = state.field_value(field);
 
-                lines.push(Line::from(format!("{prefix}{label}: {value}{suffix}")).style(style));
+                lines.push(Line::from(format!("{prefix}{label}: {value}")).style(style));
             }
         }
 
         lines.push(Line::from(""));
 
         lines.push(
-            Line::from("[j/k] navigate  [Enter] edit/confirm  [x] toggle  [Esc] close")
-                .style(Style::default().fg(palette::TEXT_DIM)),
+            Line::from(
+                "[j/k] navigate  [Enter] edit/confirm  [x] toggle  [e] $EDITOR  [Esc] close",
+            )
+            .style(self.palette.text_dim),
         );
 
         lines.push(
-            Line::from("[Ctrl+j] newline in notes").style(Style::default().fg(palette::TEXT_DIM)),
+            Line::from("[r] raw/rendered notes  [Space] toggle task  [Ctrl+j] newline in notes")
+                .style(self.palette.text_dim),
         );
 
         let paragraph = Paragraph::new(lines);
@@ -433,13 +913,20 @@ impl App {
     pub fn draw_help(&self, frame: &mut Frame<'_>) {
         let lines = match &self.ui_mode {
             UiMode::Board => vec![
-                Line::from("Weekly View").style(Style::default().fg(palette::ACTIVE)),
+                Line::from("Weekly View").style(self.palette.active),
                 Line::from(""),
                 Line::from("h/l      Move between days"),
                 Line::from("j/k      Move within column"),
                 Line::from("[/]      Previous/next week"),
+                Line::from("gt       Go to today"),
+                Line::from("gd       Go to date"),
                 Line::from("Enter    Select (drag mode)"),
                 Line::from("Space    Open todo details"),
+                Line::from("v/V      Mark todo / range"),
+                Line::from("#        Tag todo(s)"),
+                Line::from("r        Reschedule todo(s)"),
+                Line::from("/        Filter (Esc clears)"),
+                Line::from(":        Command line"),
                 Line::from("a        Add new todo"),
                 Line::from("x        Toggle completion"),
                 Line::from("dd       Delete todo"),
@@ -452,17 +939,25 @@ impl App {
                 Line::from("q/Esc    Quit"),
             ],
             UiMode::Backlog => vec![
-                Line::from("Backlog View").style(Style::default().fg(palette::ACTIVE)),
+                Line::from("Backlog View").style(self.palette.active),
                 Line::from(""),
                 Line::from("h/l      Move between columns"),
                 Line::from("j/k      Move within column"),
                 Line::from("Enter    Select (drag mode)"),
                 Line::from("Space    Open todo details"),
+                Line::from("v/V      Mark todo / range"),
+                Line::from("#        Tag todo(s)"),
+                Line::from("r        Reschedule todo(s)"),
+                Line::from("/        Filter (Esc clears)"),
+                Line::from(":        Command line"),
                 Line::from("a        Add new todo"),
                 Line::from("x        Toggle completion"),
                 Line::from("dd       Delete todo"),
                 Line::from("t        Move to today"),
                 Line::from("T        Move to tomorrow"),
+                Line::from("A/R      Add / rename column"),
+                Line::from("</>      Move column"),
+                Line::from("D        Remove column"),
                 Line::from("?        Toggle help"),
                 Line::from("b/q/Esc  Return to weekly"),
             ],
@@ -478,12 +973,13 @@ impl App {
             y: area.height.saturating_sub(height + 1),
             width,
             height,
-        };
+        }
+        .intersection(area);
 
         let block = Block::default()
             .title("Help (?)")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(palette::FOCUS));
+            .border_style(self.palette.focus);
 
         let paragraph = Paragraph::new(lines).block(block);
 
@@ -492,6 +988,21 @@ impl App {
     }
 }
 
+/// Scroll a column just far enough for the highlighted todo to be visible.
+fn scroll_offset(highlight_span: Option<(usize, usize)>, height: u16) -> u16 {
+    let Some((start, end)) = highlight_span else {
+        return 0;
+    };
+
+    let height = height as usize;
+
+    if end <= height {
+        return 0;
+    }
+
+    (end - height).min(start) as u16
+}
+
 pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,191 @@
+//! Drive the TUI without a terminal: inject services, feed scripted keys and
+//! read back the rendered buffer, for integration tests. Only built with the
+//! `test-harness` feature.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use tokio::runtime::Handle;
+
+use crate::service::Services;
+
+use super::App;
+use super::snapshot::buffer_to_string;
+
+/// An [`App`] rendered into a fixed-size in-memory terminal.
+///
+/// The app blocks on `runtime` for every service call, so the harness must
+/// be used from a thread that is not itself running async code (a plain
+/// `#[test]`, or inside `spawn_blocking`).
+pub struct Harness {
+    app: App,
+    width: u16,
+    height: u16,
+}
+
+impl Harness {
+    /// Start the app against `services` as `mach` would on launch, including
+    /// the rollover review if anything rolled over.
+    pub fn new(services: Services, runtime: Handle, width: u16, height: u16) -> Self {
+        let mut app = App::new(services, runtime);
+
+        app.start();
+
+        Self { app, width, height }
+    }
+
+    /// Feed a key script such as `"jjx"`, `"gs<Down><Enter>"` or
+    /// `"a<C-u>Buy milk<Enter>"`.
+    ///
+    /// Plain characters are typed as-is; `<...>` names a special key
+    /// (`Enter`, `Esc`, `Tab`, `BackTab`, `BS`, `Del`, `Space`, `Up`, `Down`,
+    /// `Left`, `Right`, `Home`, `End`, `PageUp`, `PageDown`, `lt`) optionally
+    /// prefixed with `C-`, `A-` or `S-`, or a single modified character such
+    /// as `<C-c>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on an unknown key name, failing the test that wrote it.
+    pub fn press(&mut self, keys: &str) -> &mut Self {
+        let keys = parse_keys(keys).unwrap_or_else(|err| panic!("{err}"));
+
+        for key in keys {
+            self.key(key);
+        }
+
+        self
+    }
+
+    /// Send a single key event.
+    pub fn key(&mut self, key: KeyEvent) -> &mut Self {
+        self.app.handle_key_event(key);
+
+        self
+    }
+
+    /// Send a mouse event. Hit testing uses the last drawn frame, so draw
+    /// one (e.g. with [`Harness::text`]) first.
+    pub fn mouse(&mut self, event: MouseEvent) -> &mut Self {
+        self.app.handle_mouse_event(event);
+
+        self
+    }
+
+    /// Press the left button at one cell and release it at another.
+    pub fn drag(&mut self, from: (u16, u16), to: (u16, u16)) -> &mut Self {
+        let event = |kind, (column, row)| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        self.mouse(event(MouseEventKind::Down(MouseButton::Left), from));
+        self.mouse(event(MouseEventKind::Up(MouseButton::Left), to))
+    }
+
+    /// Run the periodic checks the event loop performs on each tick: date
+    /// changes and writes made outside the app.
+    pub fn tick(&mut self) -> &mut Self {
+        self.app.tick();
+
+        self
+    }
+
+    /// Whether the last key asked the app to exit.
+    pub fn should_quit(&self) -> bool {
+        self.app.should_quit
+    }
+
+    pub fn services(&self) -> &Services {
+        &self.app.services
+    }
+
+    /// Draw a frame and return the buffer, styles included.
+    pub fn buffer(&mut self) -> Buffer {
+        self.app
+            .render_buffer(self.width, self.height)
+            .expect("test backend never fails to draw")
+    }
+
+    /// Draw a frame and return it as plain text, trailing spaces trimmed.
+    pub fn text(&mut self) -> String {
+        buffer_to_string(&self.buffer(), true)
+    }
+}
+
+/// Turn a key script such as `"ab<C-w><Enter>"` into key events.
+pub fn parse_keys(script: &str) -> miette::Result<Vec<KeyEvent>> {
+    let mut keys = Vec::new();
+    let mut chars = script.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '<' {
+            keys.push(char_key(ch, KeyModifiers::NONE));
+
+            continue;
+        }
+
+        let name: String = chars.by_ref().take_while(|&c| c != '>').collect();
+
+        keys.push(named_key(&name)?);
+    }
+
+    Ok(keys)
+}
+
+fn char_key(ch: char, modifiers: KeyModifiers) -> KeyEvent {
+    // Terminals report uppercase letters with SHIFT held.
+    let modifiers = if ch.is_ascii_uppercase() {
+        modifiers | KeyModifiers::SHIFT
+    } else {
+        modifiers
+    };
+
+    KeyEvent::new(KeyCode::Char(ch), modifiers)
+}
+
+fn named_key(name: &str) -> miette::Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+
+    loop {
+        let modifier = match rest.get(..2) {
+            Some("C-") => KeyModifiers::CONTROL,
+            Some("A-") => KeyModifiers::ALT,
+            Some("S-") => KeyModifiers::SHIFT,
+            _ => break,
+        };
+
+        modifiers |= modifier;
+        rest = &rest[2..];
+    }
+
+    let code = match rest {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "BS" => KeyCode::Backspace,
+        "Del" => KeyCode::Delete,
+        "Space" => KeyCode::Char(' '),
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "lt" => KeyCode::Char('<'),
+        _ => {
+            let mut chars = rest.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => return Ok(char_key(ch, modifiers)),
+                _ => miette::bail!("unknown key <{name}> in key script"),
+            }
+        }
+    };
+
+    Ok(KeyEvent::new(code, modifiers))
+}
@@ -1,20 +1,21 @@
-use chrono::NaiveDate;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
-use crate::service::config::WeekStart;
-use crate::service::todo::ReorderDirection;
+use crate::service::todo::{ListScope, ReorderDirection, Slot};
 
 use super::App;
 use super::cursor::{BacklogSelection, Horizontal, Selection, Vertical};
-use super::modes::{AddTarget, DetailField, UiMode};
-use super::state::BACKLOG_COLUMNS;
+use super::markdown;
+use super::modes::{AddTarget, DetailField, PromptKind, RolloverAction, UiMode};
+use super::status::ReportExt;
+use super::text_input::TextInput;
 
 impl App {
     pub fn handle_event(&mut self, evt: Event) {
-        if let Event::Key(key) = evt
-            && key.kind == KeyEventKind::Press
-        {
-            self.handle_key_event(key);
+        match evt {
+            Event::Key(key) if key.kind == KeyEventKind::Press => self.handle_key_event(key),
+            Event::Mouse(mouse) => self.handle_mouse_event(mouse),
+            Event::Paste(text) => self.handle_paste(text),
+            _ => {}
         }
     }
 
@@ -54,15 +55,47 @@ impl App {
 
                 return;
             }
+            UiMode::Prompt(_) => {
+                self.handle_prompt_key(key);
+
+                return;
+            }
+            UiMode::Command(_) => {
+                self.handle_command_key(key);
+
+                return;
+            }
+            UiMode::Rollover(_) => {
+                self.handle_rollover_key(key);
+
+                return;
+            }
             UiMode::Board => {}
         }
 
         if self.pending_g {
             self.pending_g = false;
-            if key.modifiers.is_empty() && matches!(key.code, KeyCode::Char('s')) {
-                self.open_settings();
+            if key.modifiers.is_empty() {
+                match key.code {
+                    KeyCode::Char('s') => {
+                        self.open_settings();
 
-                return;
+                        return;
+                    }
+                    KeyCode::Char('t') => {
+                        let today = self.services.today();
+
+                        self.jump_to_date(today).report(&mut self.status);
+
+                        return;
+                    }
+                    KeyCode::Char('d') => {
+                        self.open_prompt(PromptKind::GoTo);
+
+                        return;
+                    }
+                    _ => {}
+                }
             }
         }
 
@@ -70,9 +103,44 @@ impl App {
             self.pending_delete = false;
         }
 
+        let marking = !self.cursor.marks.is_empty();
+
         match key.code {
             KeyCode::Char('q') if key.modifiers.is_empty() => self.should_quit = true,
+            KeyCode::Esc if marking => self.cursor.marks.clear(),
+            KeyCode::Esc if self.filter.is_some() => {
+                self.set_filter("").report(&mut self.status);
+            }
+            KeyCode::Esc if self.status.has_error() => self.status.clear(),
             KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('/') => self.open_prompt(PromptKind::Filter),
+            KeyCode::Char(':') => self.open_command_line(),
+            KeyCode::Char('v') if key.modifiers.is_empty() => self.toggle_mark(),
+            KeyCode::Char('V') => self.mark_range(),
+            KeyCode::Char(' ') if marking => self.toggle_mark(),
+            KeyCode::Char('#') => self.open_prompt(PromptKind::Tag),
+            KeyCode::Char('r') if key.modifiers.is_empty() => {
+                self.open_prompt(PromptKind::Reschedule);
+            }
+            KeyCode::Char('x') if marking => {
+                self.bulk_complete(false).report(&mut self.status);
+            }
+            KeyCode::Char('s') if marking => {
+                self.bulk_move(false, ListScope::Backlog)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('t') if marking && key.modifiers.is_empty() => {
+                let today = self.services.today();
+
+                self.bulk_move(false, ListScope::Day(today))
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('T') if marking => {
+                let tomorrow = self.services.today() + chrono::Duration::days(1);
+
+                self.bulk_move(false, ListScope::Day(tomorrow))
+                    .report(&mut self.status);
+            }
             KeyCode::Char('g') if key.modifiers.is_empty() => {
                 self.pending_g = true;
             }
@@ -89,16 +157,16 @@ impl App {
             KeyCode::Char('[') => self.change_week(-1),
             KeyCode::Char(']') => self.change_week(1),
             KeyCode::Char('x') if key.modifiers.is_empty() => {
-                self.mark_complete().ok();
+                self.mark_complete().report(&mut self.status);
             }
             KeyCode::Char('s') if key.modifiers.is_empty() => {
-                self.move_to_backlog().ok();
+                self.move_to_backlog().report(&mut self.status);
             }
             KeyCode::Char('t') if key.modifiers.is_empty() => {
-                self.move_to_today().ok();
+                self.move_to_today().report(&mut self.status);
             }
             KeyCode::Char('T') if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                self.move_to_tomorrow().ok();
+                self.move_to_tomorrow().report(&mut self.status);
             }
             KeyCode::Char(' ') if key.modifiers.is_empty() => {
                 self.open_detail_board();
@@ -106,7 +174,12 @@ impl App {
             KeyCode::Enter => self.toggle_selection(),
             KeyCode::Char('d') if key.modifiers.is_empty() => {
                 if self.pending_delete {
-                    self.delete_current().ok();
+                    if marking {
+                        self.bulk_delete(false).report(&mut self.status);
+                    } else {
+                        self.delete_current().report(&mut self.status);
+                    }
+
                     self.pending_delete = false;
                 } else {
                     self.pending_delete = true;
@@ -124,32 +197,91 @@ impl App {
             self.pending_delete = false;
         }
 
+        if !matches!(key.code, KeyCode::Char('D')) {
+            self.pending_remove_column = false;
+        }
+
+        let marking = !self.backlog_cursor.marks.is_empty();
+
         match key.code {
+            KeyCode::Esc if marking => self.backlog_cursor.marks.clear(),
+            KeyCode::Esc if self.filter.is_some() => {
+                self.set_filter("").report(&mut self.status);
+            }
+            KeyCode::Char('/') => self.open_prompt(PromptKind::Filter),
+            KeyCode::Char(':') => self.open_command_line(),
+            KeyCode::Esc if self.status.has_error() => self.status.clear(),
             KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('b') => {
                 self.ui_mode = UiMode::Board;
             }
+            KeyCode::Char('v') if key.modifiers.is_empty() => self.toggle_backlog_mark(),
+            KeyCode::Char('V') => self.mark_backlog_range(),
+            KeyCode::Char(' ') if marking => self.toggle_backlog_mark(),
+            KeyCode::Char('#') => self.open_prompt(PromptKind::Tag),
+            KeyCode::Char('r') if key.modifiers.is_empty() => {
+                self.open_prompt(PromptKind::Reschedule);
+            }
+            KeyCode::Char('x') if marking => {
+                self.bulk_complete(true).report(&mut self.status);
+            }
+            KeyCode::Char('t') if marking && key.modifiers.is_empty() => {
+                let today = self.services.today();
+
+                self.bulk_move(true, ListScope::Day(today))
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('T') if marking => {
+                let tomorrow = self.services.today() + chrono::Duration::days(1);
+
+                self.bulk_move(true, ListScope::Day(tomorrow))
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('A') => self.open_prompt(PromptKind::NewColumn),
+            KeyCode::Char('R') => self.open_prompt(PromptKind::RenameColumn),
+            KeyCode::Char('<') => {
+                self.move_backlog_column(Horizontal::Left)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('>') => {
+                self.move_backlog_column(Horizontal::Right)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('D') => {
+                if self.pending_remove_column {
+                    self.pending_remove_column = false;
+
+                    self.remove_backlog_column().report(&mut self.status);
+                } else {
+                    self.confirm_remove_backlog_column()
+                        .report(&mut self.status);
+                }
+            }
             KeyCode::Char('h') => self.handle_backlog_horizontal(Horizontal::Left),
             KeyCode::Char('l') => self.handle_backlog_horizontal(Horizontal::Right),
             KeyCode::Char('j') => self.handle_backlog_vertical(Vertical::Down),
             KeyCode::Char('k') => self.handle_backlog_vertical(Vertical::Up),
             KeyCode::Enter => self.toggle_backlog_selection(),
             KeyCode::Char('x') if key.modifiers.is_empty() => {
-                self.mark_backlog_complete().ok();
+                self.mark_backlog_complete().report(&mut self.status);
             }
             KeyCode::Char('a') if key.modifiers.is_empty() => {
                 self.open_add_todo_backlog();
             }
             KeyCode::Char('t') if key.modifiers.is_empty() => {
-                self.move_backlog_to_day(0).ok();
+                self.move_backlog_to_day(0).report(&mut self.status);
             }
             KeyCode::Char('T') | KeyCode::Char('t')
                 if key.modifiers.contains(KeyModifiers::SHIFT) =>
             {
-                self.move_backlog_to_day(1).ok();
+                self.move_backlog_to_day(1).report(&mut self.status);
             }
             KeyCode::Char('d') if key.modifiers.is_empty() => {
                 if self.pending_delete {
-                    self.delete_backlog_current().ok();
+                    if marking {
+                        self.bulk_delete(true).report(&mut self.status);
+                    } else {
+                        self.delete_backlog_current().report(&mut self.status);
+                    }
 
                     self.pending_delete = false;
                 } else {
@@ -167,42 +299,66 @@ impl App {
     }
 
     pub fn handle_settings_key(&mut self, key: KeyEvent) {
-        if let UiMode::Settings(settings) = &mut self.ui_mode {
-            let mut apply: Option<WeekStart> = None;
-            let mut close = false;
+        let UiMode::Settings(ref mut state) = self.ui_mode else {
+            return;
+        };
 
+        if let Some(input) = state.editing.as_mut() {
             match key.code {
-                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => close = true,
-                KeyCode::Char('m') => {
-                    let target = WeekStart::Monday;
+                KeyCode::Esc => state.editing = None,
+                KeyCode::Enter => {
+                    let input = std::mem::take(input).into_value();
+                    let key = state.current().def.key;
 
-                    if settings.week_start != target {
-                        settings.week_start = target;
+                    state.editing = None;
 
-                        apply = Some(target);
-                    }
+                    self.set_setting(key, &input).report(&mut self.status);
+                }
+                _ => {
+                    input.handle_key(key);
                 }
-                KeyCode::Char('s') => {
-                    let target = WeekStart::Sunday;
+            }
 
-                    if settings.week_start != target {
-                        settings.week_start = target;
+            return;
+        }
 
-                        apply = Some(target);
-                    }
-                }
-                _ => {}
+        let entry = state.current().clone();
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.ui_mode = UiMode::Board,
+            KeyCode::Char('j') | KeyCode::Down => {
+                state.cursor = (state.cursor + 1).min(state.entries.len() - 1);
             }
+            KeyCode::Char('k') | KeyCode::Up => {
+                state.cursor = state.cursor.saturating_sub(1);
+            }
+            KeyCode::Char('r') => {
+                self.reset_setting(entry.def.key).report(&mut self.status);
+            }
+            KeyCode::Enter | KeyCode::Char(' ' | 'l') | KeyCode::Right
+                if entry.def.choices().is_some() =>
+            {
+                let value = entry.step(1);
 
-            let _ = settings;
+                self.set_setting(entry.def.key, value)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('h') | KeyCode::Left if entry.def.choices().is_some() => {
+                let value = entry.step(-1);
 
-            if close {
-                self.ui_mode = UiMode::Board;
+                self.set_setting(entry.def.key, value)
+                    .report(&mut self.status);
             }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let value = entry
+                    .value
+                    .as_ref()
+                    .map(|value| entry.def.format(value))
+                    .unwrap_or_default();
 
-            if let Some(new_pref) = apply {
-                self.apply_week_start(new_pref);
+                state.editing = Some(TextInput::new(value));
             }
+            _ => {}
         }
     }
 
@@ -219,12 +375,16 @@ impl App {
                 };
             }
             KeyCode::Enter => {
-                let input = std::mem::take(&mut state.input);
+                let input = std::mem::take(&mut state.input).into_value();
                 let target = state.target.clone();
 
                 if !input.trim().is_empty() {
-                    self.submit_add_todo(input.trim().to_string(), target.clone())
-                        .ok();
+                    let title = input.trim().to_string();
+
+                    self.push_add_history(title.clone());
+
+                    self.submit_add_todo(title, target.clone())
+                        .report(&mut self.status);
                 }
 
                 self.ui_mode = match target {
@@ -232,12 +392,160 @@ impl App {
                     AddTarget::BacklogColumn(_) => UiMode::Backlog,
                 };
             }
-            KeyCode::Char(c) => {
-                state.input.push(c);
+            KeyCode::Up => {
+                if self.add_history.is_empty() {
+                    return;
+                }
+
+                let index = match state.history_index {
+                    Some(idx) => idx.saturating_sub(1),
+                    None => {
+                        state.draft = state.input.value().to_string();
+
+                        self.add_history.len() - 1
+                    }
+                };
+
+                state.history_index = Some(index);
+                state.input.set_value(self.add_history[index].clone());
+            }
+            KeyCode::Down => {
+                let Some(idx) = state.history_index else {
+                    return;
+                };
+
+                if idx + 1 < self.add_history.len() {
+                    state.history_index = Some(idx + 1);
+                    state.input.set_value(self.add_history[idx + 1].clone());
+                } else {
+                    state.history_index = None;
+                    state.input.set_value(std::mem::take(&mut state.draft));
+                }
+            }
+            _ => {
+                state.input.handle_key(key);
+            }
+        }
+    }
+
+    fn push_add_history(&mut self, title: String) {
+        if self.add_history.last() != Some(&title) {
+            self.add_history.push(title);
+        }
+    }
+
+    /// Insert pasted text into whichever input currently has focus.
+    pub fn handle_paste(&mut self, text: String) {
+        match &mut self.ui_mode {
+            UiMode::AddTodo(state) => state.input.insert_str(&text),
+            UiMode::Prompt(state) => state.input.insert_str(&text),
+            UiMode::Command(state) => state.input.insert_str(&text),
+            UiMode::Detail(state) => {
+                if let Some(input) = &mut state.editing {
+                    input.insert_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_prompt_key(&mut self, key: KeyEvent) {
+        let UiMode::Prompt(ref mut state) = self.ui_mode else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.ui_mode = if state.from_backlog {
+                    UiMode::Backlog
+                } else {
+                    UiMode::Board
+                };
+            }
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut state.input).into_value();
+                let kind = state.kind;
+                let from_backlog = state.from_backlog;
+
+                self.ui_mode = if from_backlog {
+                    UiMode::Backlog
+                } else {
+                    UiMode::Board
+                };
+
+                if kind == PromptKind::Filter {
+                    self.set_filter(&input).report(&mut self.status);
+
+                    return;
+                }
+
+                if input.trim().is_empty() {
+                    return;
+                }
+
+                match kind {
+                    PromptKind::Filter => None,
+                    PromptKind::GoTo => self.jump_to_input(&input).report(&mut self.status),
+                    PromptKind::Tag => self.bulk_tag(from_backlog, &input).report(&mut self.status),
+                    PromptKind::Reschedule => self
+                        .bulk_reschedule(from_backlog, &input)
+                        .report(&mut self.status),
+                    PromptKind::NewColumn => {
+                        self.add_backlog_column(&input).report(&mut self.status)
+                    }
+                    PromptKind::RenameColumn => {
+                        self.rename_backlog_column(&input).report(&mut self.status)
+                    }
+                };
             }
-            KeyCode::Backspace => {
-                state.input.pop();
+            _ => {
+                state.input.handle_key(key);
             }
+        }
+    }
+
+    pub fn handle_rollover_key(&mut self, key: KeyEvent) {
+        let UiMode::Rollover(ref mut state) = self.ui_mode else {
+            return;
+        };
+
+        if let Some(input) = state.reschedule.as_mut() {
+            match key.code {
+                KeyCode::Esc => state.reschedule = None,
+                KeyCode::Enter => {
+                    let input = input.value().to_string();
+
+                    self.reschedule_rollover(&input).report(&mut self.status);
+                }
+                _ => {
+                    input.handle_key(key);
+                }
+            }
+
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if state.cursor + 1 < state.items.len() => {
+                state.cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                state.cursor = state.cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                self.resolve_rollover(RolloverAction::Keep)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('s') => {
+                self.resolve_rollover(RolloverAction::Backlog)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('d') => {
+                self.resolve_rollover(RolloverAction::Delete)
+                    .report(&mut self.status);
+            }
+            KeyCode::Char('r') => state.reschedule = Some(TextInput::default()),
+            KeyCode::Esc | KeyCode::Char('q') => self.ui_mode = UiMode::Board,
             _ => {}
         }
     }
@@ -263,23 +571,48 @@ impl App {
                     UiMode::Board
                 };
 
-                self.refresh_board().ok();
+                self.refresh_board().report(&mut self.status);
 
-                self.refresh_backlog().ok();
+                self.refresh_backlog().report(&mut self.status);
             }
             KeyCode::Char('j') => {
                 let UiMode::Detail(ref mut state) = self.ui_mode else {
                     return;
                 };
 
-                state.field = state.field.next();
+                if state.field == DetailField::Notes && !state.raw_notes {
+                    let count = markdown::task_count(&state.notes);
+
+                    state.task_cursor = match state.task_cursor {
+                        None if count > 0 => Some(0),
+                        Some(idx) if idx + 1 < count => Some(idx + 1),
+                        other => other,
+                    };
+                } else {
+                    state.field = state.field.next();
+                }
             }
             KeyCode::Char('k') => {
                 let UiMode::Detail(ref mut state) = self.ui_mode else {
                     return;
                 };
 
-                state.field = state.field.prev();
+                match state.task_cursor {
+                    Some(0) => state.task_cursor = None,
+                    Some(idx) => state.task_cursor = Some(idx - 1),
+                    None => state.field = state.field.prev(),
+                }
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_detail_task();
+            }
+            KeyCode::Char('r') => {
+                let UiMode::Detail(ref mut state) = self.ui_mode else {
+                    return;
+                };
+
+                state.raw_notes = !state.raw_notes;
+                state.task_cursor = None;
             }
             KeyCode::Enter => {
                 let UiMode::Detail(ref mut state) = self.ui_mode else {
@@ -287,12 +620,16 @@ impl App {
                 };
 
                 if state.field.is_editable() {
-                    state.editing = Some(state.field_value(state.field));
+                    state.task_cursor = None;
+                    state.begin_edit();
                 }
             }
             KeyCode::Char('x') => {
                 self.toggle_detail_status();
             }
+            KeyCode::Char('e') => {
+                self.external_edit = true;
+            }
             _ => {}
         }
     }
@@ -306,25 +643,16 @@ impl App {
             return;
         };
 
-        let is_notes = state.field == DetailField::Notes;
-
         match key.code {
             KeyCode::Esc => {
                 self.finish_detail_edit(false);
             }
-            KeyCode::Char('j') if is_notes && key.modifiers.contains(KeyModifiers::CONTROL) => {
-                input.push('\n');
-            }
             KeyCode::Enter => {
                 self.finish_detail_edit(true);
             }
-            KeyCode::Char(c) => {
-                input.push(c);
-            }
-            KeyCode::Backspace => {
-                input.pop();
+            _ => {
+                input.handle_key(key);
             }
-            _ => {}
         }
     }
 
@@ -333,7 +661,7 @@ impl App {
             return;
         };
 
-        let Some(input) = state.editing.take() else {
+        let Some(input) = state.editing.take().map(TextInput::into_value) else {
             return;
         };
 
@@ -344,78 +672,15 @@ impl App {
         let id = state.todo_id;
         let field = state.field;
 
-        match field {
-            DetailField::Title => {
-                if !input.trim().is_empty()
-                    && self
-                        .runtime
-                        .block_on(
-                            self.services
-                                .todos
-                                .update_title(id, input.trim().to_string()),
-                        )
-                        .is_ok()
-                {
-                    let UiMode::Detail(ref mut state) = self.ui_mode else {
-                        return;
-                    };
-
-                    state.title = input.trim().to_string();
-                }
-            }
-            DetailField::Date => {
-                let new_date = if input.trim().eq_ignore_ascii_case("none")
-                    || input.trim().eq_ignore_ascii_case("someday")
-                    || input.trim().is_empty()
-                {
-                    Some(None)
-                } else {
-                    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
-                        .ok()
-                        .map(Some)
-                };
-
-                if let Some(date) = new_date
-                    && self
-                        .runtime
-                        .block_on(self.services.todos.update_scheduled_for(id, date))
-                        .is_ok()
-                {
-                    let UiMode::Detail(ref mut state) = self.ui_mode else {
-                        return;
-                    };
-
-                    state.date = date;
-                }
-            }
-            DetailField::Notes => {
-                let notes = if input.trim().is_empty() {
-                    None
-                } else {
-                    Some(input.clone())
-                };
-
-                if self
-                    .runtime
-                    .block_on(self.services.todos.update_notes(id, notes))
-                    .is_ok()
-                {
-                    let UiMode::Detail(ref mut state) = self.ui_mode else {
-                        return;
-                    };
-
-                    state.notes = input;
-                }
-            }
-            DetailField::Status => {}
-        }
+        self.save_detail_field(id, field, input)
+            .report(&mut self.status);
     }
 
     pub fn handle_horizontal(&mut self, dir: Horizontal) {
         let day_count = self.state.columns.len();
 
         if self.cursor.selection.is_some() {
-            self.move_selected_horizontal(dir).ok();
+            self.move_selected_horizontal(dir).report(&mut self.status);
         } else {
             match dir {
                 Horizontal::Left => {
@@ -426,7 +691,7 @@ impl App {
 
                         self.board.reset(day_count);
 
-                        self.refresh_board().ok();
+                        self.refresh_board().report(&mut self.status);
                     } else {
                         self.cursor.focus -= 1;
                     }
@@ -439,7 +704,7 @@ impl App {
 
                         self.board.reset(day_count);
 
-                        self.refresh_board().ok();
+                        self.refresh_board().report(&mut self.status);
                     } else {
                         self.cursor.focus += 1;
                     }
@@ -456,7 +721,7 @@ impl App {
                 Vertical::Down => ReorderDirection::Down,
             };
 
-            self.reorder_selected(reorder_dir).ok();
+            self.reorder_selected(reorder_dir).report(&mut self.status);
         } else {
             self.cursor.move_vertical(dir, &self.board);
         }
@@ -480,9 +745,10 @@ impl App {
 
     pub fn handle_backlog_horizontal(&mut self, dir: Horizontal) {
         if self.backlog_cursor.selection.is_some() {
-            self.move_backlog_selected_horizontal(dir).ok();
+            self.move_backlog_selected_horizontal(dir)
+                .report(&mut self.status);
         } else {
-            self.backlog_cursor.move_horizontal(dir);
+            self.backlog_cursor.move_horizontal(dir, &self.board);
         }
     }
 
@@ -493,7 +759,8 @@ impl App {
                 Vertical::Down => ReorderDirection::Down,
             };
 
-            self.reorder_backlog_selected(reorder_dir).ok();
+            self.reorder_backlog_selected(reorder_dir)
+                .report(&mut self.status);
         } else {
             self.backlog_cursor.move_vertical(dir, &self.board);
         }
@@ -531,7 +798,7 @@ impl App {
         self.cursor
             .sync_after_refresh(self.state.columns.len(), &self.board);
 
-        self.refresh_board().ok();
+        self.refresh_board().report(&mut self.status);
     }
 
     pub fn move_backlog_selected_horizontal(&mut self, dir: Horizontal) -> miette::Result<()> {
@@ -548,7 +815,7 @@ impl App {
                 selection.column - 1
             }
             Horizontal::Right => {
-                if selection.column + 1 >= BACKLOG_COLUMNS {
+                if selection.column + 1 >= self.board.backlog_columns.len() {
                     return Ok(());
                 }
 
@@ -556,11 +823,11 @@ impl App {
             }
         };
 
-        self.runtime.block_on(
-            self.services
-                .todos
-                .set_backlog_column(selection.id, target_col as i64),
-        )?;
+        self.runtime.block_on(self.services.todos.move_to_backlog(
+            selection.id,
+            target_col as i64,
+            Slot::Top,
+        ))?;
 
         self.refresh_backlog()?;
 
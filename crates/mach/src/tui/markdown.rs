@@ -0,0 +1,320 @@
+//! Minimal Markdown renderer for todo notes.
+//!
+//! Supports the subset we actually write in notes: ATX headings, bullet
+//! and task lists, block quotes, fenced code, and inline bold,
+//! italic, code and links. Anything else is shown as plain text.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::palette::Palette;
+
+/// Render `text` into styled lines. `active_task` highlights the n-th task
+/// list item so it can be toggled from the detail modal.
+pub fn render(
+    text: &str,
+    base: Style,
+    active_task: Option<usize>,
+    palette: &Palette,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    let mut task_idx = 0;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+
+            continue;
+        }
+
+        if in_code {
+            lines.push(Line::from(Span::styled(
+                format!("  {raw}"),
+                base.patch(palette.text_dim),
+            )));
+
+            continue;
+        }
+
+        let indent = &raw[..raw.len() - trimmed.len()];
+
+        if let Some((level, heading)) = parse_heading(trimmed) {
+            let mut style = base.add_modifier(Modifier::BOLD);
+
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+
+            lines.push(Line::from(inline(heading, style, palette)));
+
+            continue;
+        }
+
+        if let Some((done, rest)) = parse_task(trimmed) {
+            let is_active = active_task == Some(task_idx);
+            task_idx += 1;
+
+            let marker = if done { "☑ " } else { "☐ " };
+
+            let style = if done {
+                base.patch(palette.text_dim)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                base
+            };
+
+            let marker_style = if is_active {
+                base.patch(palette.accent).add_modifier(Modifier::BOLD)
+            } else {
+                base
+            };
+
+            let mut spans = vec![
+                Span::raw(indent.to_string()),
+                Span::styled(marker, marker_style),
+            ];
+
+            spans.extend(inline(rest, style, palette));
+
+            lines.push(Line::from(spans));
+
+            continue;
+        }
+
+        if let Some(rest) = parse_bullet(trimmed) {
+            let mut spans = vec![Span::styled(format!("{indent}• "), base)];
+
+            spans.extend(inline(rest, base, palette));
+            lines.push(Line::from(spans));
+
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("> ") {
+            let mut spans = vec![Span::styled("│ ", base.patch(palette.border))];
+
+            spans.extend(inline(rest, base.add_modifier(Modifier::ITALIC), palette));
+            lines.push(Line::from(spans));
+
+            continue;
+        }
+
+        let mut spans = vec![Span::raw(indent.to_string())];
+
+        spans.extend(inline(trimmed, base, palette));
+        lines.push(Line::from(spans));
+    }
+
+    lines
+}
+
+/// Number of task list items (`- [ ]` / `- [x]`, or `1. [ ]`) outside code
+/// blocks.
+pub fn task_count(text: &str) -> usize {
+    task_lines(text).count()
+}
+
+/// Flip the checkbox of the n-th task list item, returning the new text.
+pub fn toggle_task(text: &str, index: usize) -> String {
+    let Some(line_no) = task_lines(text).nth(index) else {
+        return text.to_string();
+    };
+
+    let mut out: Vec<String> = text.lines().map(str::to_string).collect();
+    let line = &mut out[line_no];
+
+    // Only the box right after the list marker; a `[ ]` later in the title
+    // is just text.
+    let trimmed = line.trim_start();
+    let Some(rest) = list_item(trimmed) else {
+        return text.to_string();
+    };
+    let pos = line.len() - rest.len();
+
+    let flipped = if rest.starts_with("[ ]") {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+
+    line.replace_range(pos..pos + 3, flipped);
+
+    let mut joined = out.join("\n");
+
+    if text.ends_with('\n') {
+        joined.push('\n');
+    }
+
+    joined
+}
+
+/// Line numbers of every task item, skipping fenced code.
+fn task_lines(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut in_code = false;
+
+    text.lines().enumerate().filter_map(move |(idx, raw)| {
+        let trimmed = raw.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+
+            return None;
+        }
+
+        (!in_code && parse_task(trimmed).is_some()).then_some(idx)
+    })
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+
+    if level == 0 || level > 6 {
+        return None;
+    }
+
+    line[level..].strip_prefix(' ').map(|rest| (level, rest))
+}
+
+fn parse_task(line: &str) -> Option<(bool, &str)> {
+    let rest = list_item(line)?;
+
+    if let Some(rest) = rest.strip_prefix("[ ] ") {
+        return Some((false, rest));
+    }
+
+    rest.strip_prefix("[x] ")
+        .or_else(|| rest.strip_prefix("[X] "))
+        .map(|rest| (true, rest))
+}
+
+fn parse_bullet(line: &str) -> Option<&str> {
+    ["- ", "* ", "+ "]
+        .into_iter()
+        .find_map(|marker| line.strip_prefix(marker))
+}
+
+/// The text after a bullet or ordered (`1. `) list marker.
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(rest) = parse_bullet(line) {
+        return Some(rest);
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+
+    (digits > 0)
+        .then(|| line[digits..].strip_prefix(". "))
+        .flatten()
+}
+
+/// Parse inline emphasis, code spans and links.
+fn inline(text: &str, base: Style, palette: &Palette) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut buf = String::new();
+    let mut bold = false;
+    let mut italic = false;
+    let mut rest = text;
+
+    let style_for = |bold: bool, italic: bool| {
+        let mut style = base;
+
+        if bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+
+        if italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+
+        style
+    };
+
+    macro_rules! flush {
+        () => {
+            if !buf.is_empty() {
+                spans.push(Span::styled(
+                    std::mem::take(&mut buf),
+                    style_for(bold, italic),
+                ));
+            }
+        };
+    }
+
+    while let Some(ch) = rest.chars().next() {
+        if rest.starts_with("**") || rest.starts_with("__") {
+            let marker = &rest[..2];
+
+            if bold || rest[2..].contains(marker) {
+                flush!();
+                bold = !bold;
+                rest = &rest[2..];
+
+                continue;
+            }
+        }
+
+        if ch == '*' || (ch == '_' && (italic || buf.is_empty() || buf.ends_with(' '))) {
+            let marker = &rest[..1];
+
+            if italic || rest[1..].contains(marker) {
+                flush!();
+                italic = !italic;
+                rest = &rest[1..];
+
+                continue;
+            }
+        }
+
+        if ch == '`'
+            && let Some(end) = rest[1..].find('`')
+        {
+            flush!();
+
+            spans.push(Span::styled(
+                rest[1..end + 1].to_string(),
+                base.patch(palette.accent),
+            ));
+
+            rest = &rest[end + 2..];
+
+            continue;
+        }
+
+        if ch == '['
+            && let Some(close) = rest.find("](")
+            && let Some(end) = rest[close..].find(')')
+        {
+            flush!();
+
+            let label = &rest[1..close];
+            let url = &rest[close + 2..close + end];
+
+            spans.push(Span::styled(
+                label.to_string(),
+                base.patch(palette.focus).add_modifier(Modifier::UNDERLINED),
+            ));
+
+            if url != label {
+                spans.push(Span::styled(
+                    format!(" ({url})"),
+                    base.patch(palette.text_dim),
+                ));
+            }
+
+            rest = &rest[close + end + 1..];
+
+            continue;
+        }
+
+        buf.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    flush!();
+
+    spans
+}
@@ -1,23 +1,41 @@
+use std::cell::RefCell;
 use std::time::{Duration, Instant};
 
 use crossterm::event;
 use miette::{Context, IntoDiagnostic};
 use tokio::runtime::Handle;
 
-use crate::service::{Services, config::WeekStart};
+use crate::service::{
+    Services,
+    config::{TitleOverflow, WeekStart},
+    watch::ChangeWatcher,
+};
 
 mod actions;
+mod columns;
+mod command;
 mod cursor;
 mod draw;
+#[cfg(feature = "test-harness")]
+pub mod harness;
 mod input;
+pub mod markdown;
 mod modes;
+mod mouse;
 pub mod palette;
+pub mod snapshot;
 mod state;
+mod status;
 mod terminal;
+pub mod text;
+pub mod text_input;
 
 use cursor::{BacklogCursor, CursorState};
-use modes::UiMode;
+use modes::{ActiveFilter, UiMode};
+use mouse::{ColumnHit, DragState, LastClick};
+use palette::Palette;
 use state::{BoardData, WeekState};
+use status::{ReportExt, StatusLine};
 use terminal::{TerminalGuard, setup_terminal};
 
 /// Launch the Ratatui application, blocking on the UI event loop.
@@ -43,17 +61,33 @@ pub struct App {
     cursor: CursorState,
     backlog_cursor: BacklogCursor,
     week_pref: WeekStart,
+    overflow_pref: TitleOverflow,
+    palette: Palette,
     ui_mode: UiMode,
     pending_g: bool,
     pending_delete: bool,
+    pending_remove_column: bool,
     should_quit: bool,
     show_help: bool,
+    hits: RefCell<Vec<ColumnHit>>,
+    drag: Option<DragState>,
+    last_click: Option<LastClick>,
+    add_history: Vec<String>,
+    command_history: Vec<String>,
+    external_edit: bool,
+    filter: Option<ActiveFilter>,
+    watcher: Option<ChangeWatcher>,
+    status: StatusLine,
+    /// Off for one-shot renders (`mach week`), which have no cursor or
+    /// messages to show.
+    status_bar: bool,
 }
 
 impl App {
     fn new(services: Services, runtime: Handle) -> Self {
         let today = services.today();
         let week_pref = services.week_start();
+        let overflow_pref = services.title_overflow();
 
         let state = WeekState::new(today, week_pref);
         let board = BoardData::new(state.columns.len());
@@ -63,6 +97,25 @@ impl App {
             cursor.set_focus_row(idx, 0);
         }
 
+        // Without a watcher the board still works, it just won't notice
+        // writes from other processes.
+        let watcher = runtime
+            .block_on(ChangeWatcher::new(services.todos.connection()))
+            .ok();
+
+        // A bad `color.*` override is skipped and reported once the board
+        // is up.
+        let mut status = StatusLine::default();
+        let (palette, problem) = Palette::resolve(
+            services.theme(),
+            services.color_overrides(),
+            palette::no_color(),
+        );
+
+        if let Some(err) = problem {
+            status.error(&err);
+        }
+
         Self {
             services,
             runtime,
@@ -71,16 +124,35 @@ impl App {
             cursor,
             backlog_cursor: BacklogCursor::new(),
             week_pref,
+            overflow_pref,
+            palette,
             ui_mode: UiMode::Board,
             pending_g: false,
             pending_delete: false,
+            pending_remove_column: false,
             should_quit: false,
             show_help: false,
+            hits: RefCell::new(Vec::new()),
+            drag: None,
+            last_click: None,
+            add_history: Vec::new(),
+            command_history: Vec::new(),
+            external_edit: false,
+            filter: None,
+            watcher,
+            status,
+            status_bar: true,
         }
     }
 
+    /// Load the board and queue the rollover review, as on startup.
+    fn start(&mut self) {
+        self.refresh_board().report(&mut self.status);
+        self.open_rollover_review().report(&mut self.status);
+    }
+
     fn run(&mut self) -> miette::Result<()> {
-        self.refresh_board().ok();
+        self.start();
 
         let mut terminal = setup_terminal()?;
         let _guard = TerminalGuard;
@@ -108,11 +180,43 @@ impl App {
                 self.handle_event(evt);
             }
 
+            if std::mem::take(&mut self.external_edit) {
+                self.edit_notes_in_editor(&mut terminal)?;
+            }
+
             if last_tick.elapsed() >= tick_rate {
                 last_tick = Instant::now();
+
+                self.tick();
             }
         }
 
         Ok(())
     }
+
+    /// Periodic housekeeping between key presses.
+    fn tick(&mut self) {
+        self.check_date();
+        self.check_external_changes();
+    }
+
+    /// Follow the clock if the date changed since the last check.
+    fn check_date(&mut self) {
+        let today = self.services.current_date();
+
+        if today != self.services.today() {
+            self.handle_day_change(today).report(&mut self.status);
+        }
+    }
+
+    /// Reload if something was written since the board was last loaded.
+    fn check_external_changes(&mut self) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+
+        if self.runtime.block_on(watcher.changed()).unwrap_or(false) {
+            self.handle_external_change().report(&mut self.status);
+        }
+    }
 }
@@ -1,7 +1,11 @@
 use chrono::NaiveDate;
 use uuid::Uuid;
 
-use crate::service::config::WeekStart;
+use crate::service::settings::SettingEntry;
+use crate::service::todo::TodoQuery;
+
+use super::state::TodoView;
+use super::text_input::TextInput;
 
 pub enum UiMode {
     Board,
@@ -9,17 +13,152 @@ pub enum UiMode {
     Settings(SettingsState),
     AddTodo(AddTodoState),
     Detail(DetailState),
+    Prompt(PromptState),
+    Command(CommandState),
+    Rollover(RolloverState),
 }
 
 #[derive(Clone)]
 pub struct SettingsState {
-    pub week_start: WeekStart,
+    pub entries: Vec<SettingEntry>,
+    pub cursor: usize,
+    /// Text being typed for the focused setting.
+    pub editing: Option<TextInput>,
+}
+
+impl SettingsState {
+    pub fn new(entries: Vec<SettingEntry>) -> Self {
+        Self {
+            entries,
+            cursor: 0,
+            editing: None,
+        }
+    }
+
+    pub fn current(&self) -> &SettingEntry {
+        &self.entries[self.cursor]
+    }
 }
 
 #[derive(Clone)]
 pub struct AddTodoState {
-    pub input: String,
+    pub input: TextInput,
     pub target: AddTarget,
+    /// Position in the add history while browsing with `Up`/`Down`.
+    pub history_index: Option<usize>,
+    /// Text typed before browsing history, restored when stepping past the newest entry.
+    pub draft: String,
+}
+
+impl AddTodoState {
+    pub fn new(target: AddTarget) -> Self {
+        Self {
+            input: TextInput::default(),
+            target,
+            history_index: None,
+            draft: String::new(),
+        }
+    }
+}
+
+/// One-line prompt for actions that need an argument.
+#[derive(Clone)]
+pub struct PromptState {
+    pub input: TextInput,
+    pub kind: PromptKind,
+    pub from_backlog: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// `+tag` adds, `-tag` removes.
+    Tag,
+    /// Date expression, or `someday` for the backlog.
+    Reschedule,
+    /// Query expression narrowing what the board and backlog show.
+    Filter,
+    /// Date expression to show on the board.
+    GoTo,
+    /// Name for a new backlog column.
+    NewColumn,
+    /// New name for the focused backlog column.
+    RenameColumn,
+}
+
+impl PromptKind {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Tag => "Tag (+add / -remove)",
+            Self::Reschedule => "Reschedule to",
+            Self::Filter => "Filter",
+            Self::GoTo => "Go to date",
+            Self::NewColumn => "New column",
+            Self::RenameColumn => "Rename column",
+        }
+    }
+
+    /// Whether the prompt applies to the marked or focused todos.
+    pub fn acts_on_todos(self) -> bool {
+        matches!(self, Self::Tag | Self::Reschedule)
+    }
+}
+
+/// The `:` command line.
+#[derive(Clone)]
+pub struct CommandState {
+    pub input: TextInput,
+    pub from_backlog: bool,
+    /// Position in the command history while browsing with `Up`/`Down`.
+    pub history_index: Option<usize>,
+    /// Text typed before browsing history, restored when stepping past the newest entry.
+    pub draft: String,
+    /// Candidates being cycled with `Tab`.
+    pub completion: Option<Completion>,
+}
+
+impl CommandState {
+    pub fn new(from_backlog: bool) -> Self {
+        Self {
+            input: TextInput::default(),
+            from_backlog,
+            history_index: None,
+            draft: String::new(),
+            completion: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Completion {
+    /// The line before the word being completed.
+    pub prefix: String,
+    pub candidates: Vec<String>,
+    pub index: usize,
+}
+
+/// Startup review of todos that rolled over to today.
+#[derive(Clone)]
+pub struct RolloverState {
+    pub items: Vec<TodoView>,
+    pub cursor: usize,
+    /// Date input while rescheduling the highlighted todo.
+    pub reschedule: Option<TextInput>,
+}
+
+/// What to do with a rolled-over todo.
+#[derive(Clone, Copy)]
+pub enum RolloverAction {
+    Keep,
+    Backlog,
+    Delete,
+    Reschedule(NaiveDate),
+}
+
+/// Query applied to the board and backlog via `/`.
+#[derive(Clone)]
+pub struct ActiveFilter {
+    pub text: String,
+    pub query: TodoQuery,
 }
 
 #[derive(Clone)]
@@ -77,11 +216,26 @@ pub struct DetailState {
     pub status: String,
     pub notes: String,
     pub field: DetailField,
-    pub editing: Option<String>,
+    pub editing: Option<TextInput>,
     pub from_backlog: bool,
+    /// Show notes as raw text instead of rendered Markdown.
+    pub raw_notes: bool,
+    /// Task list item focused inside the notes, toggled with `Space`.
+    pub task_cursor: Option<usize>,
 }
 
 impl DetailState {
+    /// Start editing the focused field with its current value.
+    pub fn begin_edit(&mut self) {
+        let value = self.field_value(self.field);
+
+        self.editing = Some(if self.field == DetailField::Notes {
+            TextInput::multiline(value)
+        } else {
+            TextInput::new(value)
+        });
+    }
+
     pub fn field_value(&self, field: DetailField) -> String {
         match field {
             DetailField::Title => self.title.clone(),
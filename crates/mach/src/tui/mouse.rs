@@ -0,0 +1,277 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use uuid::Uuid;
+
+use crate::service::todo::{ListScope, Slot};
+
+use super::App;
+use super::modes::UiMode;
+use super::state::TodoView;
+use super::status::ReportExt;
+
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Column a mouse position can land on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitColumn {
+    Day(usize),
+    Backlog(usize),
+}
+
+/// Screen geometry of a rendered column, recorded while drawing.
+#[derive(Debug, Clone)]
+pub struct ColumnHit {
+    pub target: HitColumn,
+    pub area: Rect,
+    pub body_y: u16,
+    pub scroll: u16,
+    pub spans: Vec<(usize, usize)>,
+}
+
+impl ColumnHit {
+    fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.area.x
+            && x < self.area.x + self.area.width
+            && y >= self.area.y
+            && y < self.area.y + self.area.height
+    }
+
+    fn row_at(&self, y: u16) -> Option<usize> {
+        if y < self.body_y {
+            return None;
+        }
+
+        let line = (y - self.body_y + self.scroll) as usize;
+
+        self.spans
+            .iter()
+            .position(|&(start, end)| line >= start && line < end)
+    }
+}
+
+/// Todo currently being dragged with the left mouse button.
+#[derive(Debug, Clone, Copy)]
+pub struct DragState {
+    pub id: Uuid,
+    pub from: HitColumn,
+    pub row: usize,
+}
+
+/// Last click, used to detect double clicks on the same todo.
+#[derive(Debug, Clone, Copy)]
+pub struct LastClick {
+    pub id: Uuid,
+    pub at: Instant,
+}
+
+impl App {
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        if self.show_help || !matches!(self.ui_mode, UiMode::Board | UiMode::Backlog) {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.mouse_down(mouse.column, mouse.row),
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.mouse_up(mouse.column, mouse.row)
+                    .report(&mut self.status);
+            }
+            MouseEventKind::ScrollDown if matches!(self.ui_mode, UiMode::Board) => {
+                self.change_week(1);
+            }
+            MouseEventKind::ScrollUp if matches!(self.ui_mode, UiMode::Board) => {
+                self.change_week(-1);
+            }
+            _ => {}
+        }
+    }
+
+    fn hit_test(&self, x: u16, y: u16) -> Option<(HitColumn, Option<usize>)> {
+        self.hits
+            .borrow()
+            .iter()
+            .find(|hit| hit.contains(x, y))
+            .map(|hit| (hit.target, hit.row_at(y)))
+    }
+
+    fn todo_at(&self, column: HitColumn, row: usize) -> Option<Uuid> {
+        match column {
+            HitColumn::Day(col) => self.board.day_todo_id_at(col, row),
+            HitColumn::Backlog(col) => self.board.backlog_todo_id_at(col, row),
+        }
+    }
+
+    fn mouse_down(&mut self, x: u16, y: u16) {
+        self.pending_g = false;
+        self.pending_delete = false;
+        self.pending_remove_column = false;
+
+        let Some((column, row)) = self.hit_test(x, y) else {
+            return;
+        };
+
+        self.focus_hit(column, row);
+
+        let Some(row) = row else {
+            self.last_click = None;
+
+            return;
+        };
+
+        let Some(id) = self.todo_at(column, row) else {
+            return;
+        };
+
+        let now = Instant::now();
+
+        let is_double = self
+            .last_click
+            .is_some_and(|last| last.id == id && now.duration_since(last.at) <= DOUBLE_CLICK);
+
+        if is_double {
+            self.last_click = None;
+            self.drag = None;
+
+            self.open_detail(id, matches!(column, HitColumn::Backlog(_)));
+
+            return;
+        }
+
+        self.last_click = Some(LastClick { id, at: now });
+        self.drag = Some(DragState {
+            id,
+            from: column,
+            row,
+        });
+    }
+
+    fn focus_hit(&mut self, column: HitColumn, row: Option<usize>) {
+        match column {
+            HitColumn::Day(col) => {
+                self.cursor
+                    .set_focus_row(col, row.unwrap_or(self.cursor.day_rows[col]));
+            }
+            HitColumn::Backlog(col) => {
+                self.backlog_cursor.column = col;
+                self.backlog_cursor.selection = None;
+
+                if let Some(row) = row {
+                    self.backlog_cursor.rows[col] = row;
+                }
+            }
+        }
+    }
+
+    fn mouse_up(&mut self, x: u16, y: u16) -> miette::Result<()> {
+        let Some(drag) = self.drag.take() else {
+            return Ok(());
+        };
+
+        let Some((target, row)) = self.hit_test(x, y) else {
+            return Ok(());
+        };
+
+        let same_view = matches!(
+            (drag.from, target),
+            (HitColumn::Day(_), HitColumn::Day(_)) | (HitColumn::Backlog(_), HitColumn::Backlog(_))
+        );
+
+        if !same_view || (drag.from == target && row.is_none_or(|row| row == drag.row)) {
+            return Ok(());
+        }
+
+        let Some(position) = self.drop_position(&drag, target, row) else {
+            return Ok(());
+        };
+        let todos = &self.services.todos;
+
+        match target {
+            HitColumn::Day(col) => {
+                let date = self.state.columns[col].date;
+
+                self.runtime.block_on(todos.move_to_position(
+                    drag.id,
+                    ListScope::Day(date),
+                    position,
+                ))?;
+            }
+            HitColumn::Backlog(col) => {
+                self.runtime.block_on(todos.move_to_backlog(
+                    drag.id,
+                    col as i64,
+                    Slot::At(position),
+                ))?;
+            }
+        }
+
+        match (drag.from, target) {
+            (HitColumn::Backlog(_), HitColumn::Backlog(_)) => self.refresh_backlog()?,
+            _ => self.refresh_board()?,
+        }
+
+        self.last_click = None;
+
+        match target {
+            HitColumn::Day(_) => {
+                if let Some((col, row)) = self.board.find_day_position(drag.id) {
+                    self.cursor.set_focus_row(col, row);
+                }
+            }
+            HitColumn::Backlog(_) => {
+                if let Some((col, row)) = self.board.find_backlog_position(drag.id) {
+                    self.backlog_cursor.column = col;
+                    self.backlog_cursor.rows[col] = row;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Where `drag` lands among the todos it is ordered with in `target`
+    /// when dropped on `row`: in place of the todo there, or at the bottom
+    /// when dropped below the last one.
+    fn drop_position(
+        &self,
+        drag: &DragState,
+        target: HitColumn,
+        row: Option<usize>,
+    ) -> Option<usize> {
+        let todos = match target {
+            HitColumn::Day(col) => self.board.days.get(col)?,
+            HitColumn::Backlog(col) => self.board.backlog_columns.get(col)?,
+        };
+
+        let dragged = self.board_todo(drag)?;
+
+        let row = row.unwrap_or(usize::MAX);
+        let done = dragged.status == "done";
+
+        // Dragging down within a column lands after the todo under the
+        // pointer, since the dragged todo no longer sits above it.
+        let take = if drag.from == target && row > drag.row {
+            row.saturating_add(1)
+        } else {
+            row
+        };
+
+        let position = todos
+            .iter()
+            .take(take)
+            .filter(|todo| todo.id != drag.id && (todo.status == "done") == done)
+            .count();
+
+        Some(position)
+    }
+
+    /// The dragged todo, if the board still shows it where the drag began.
+    fn board_todo(&self, drag: &DragState) -> Option<&TodoView> {
+        match drag.from {
+            HitColumn::Day(col) => self.board.days.get(col)?.get(drag.row),
+            HitColumn::Backlog(col) => self.board.backlog_columns.get(col)?.get(drag.row),
+        }
+        .filter(|todo| todo.id == drag.id)
+    }
+}
@@ -1,14 +1,159 @@
-#![allow(dead_code)]
-use ratatui::style::Color;
+//! Styles for each UI role, taken from a named [`Theme`] with per-role color
+//! overrides from config. The app keeps the palette in effect and passes it
+//! to everything that draws.
 
-// Text
-pub const TEXT: Color = Color::Reset;
-pub const TEXT_DIM: Color = Color::DarkGray;
+use std::collections::BTreeMap;
 
-// States (hierarchy: ACCENT > ACTIVE > FOCUS)
-pub const FOCUS: Color = Color::LightBlue;
-pub const ACTIVE: Color = Color::Yellow;
-pub const ACCENT: Color = Color::Magenta;
+use ratatui::style::{Color, Modifier, Style};
 
-// Chrome
-pub const BORDER: Color = Color::DarkGray;
+use crate::service::config::Theme;
+
+/// Role names as used in `color.<role>` config keys.
+pub const ROLES: [&str; 9] = [
+    "text", "text_dim", "focus", "active", "accent", "stale", "error", "border", "tag",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    // Text
+    pub text: Style,
+    pub text_dim: Style,
+    // States (hierarchy: accent > active > focus)
+    pub focus: Style,
+    pub active: Style,
+    pub accent: Style,
+    // Todos that keep rolling over
+    pub stale: Style,
+    // Failures reported in the status bar
+    pub error: Style,
+    // Chrome
+    pub border: Style,
+    // `#tag` labels on todos
+    pub tag: Style,
+}
+
+impl Palette {
+    pub fn theme(theme: Theme) -> Self {
+        let fg = |color| Style::default().fg(color);
+        let bold = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+
+        match theme {
+            Theme::Default => Self {
+                text: fg(Color::Reset),
+                text_dim: fg(Color::DarkGray),
+                focus: fg(Color::LightBlue),
+                active: fg(Color::Yellow),
+                accent: fg(Color::Magenta),
+                stale: fg(Color::LightRed),
+                error: fg(Color::Red),
+                border: fg(Color::DarkGray),
+                tag: fg(Color::Cyan),
+            },
+            Theme::HighContrast => Self {
+                text: fg(Color::Reset),
+                text_dim: fg(Color::Gray),
+                focus: bold(Color::LightCyan),
+                active: bold(Color::LightYellow),
+                accent: bold(Color::LightMagenta),
+                stale: bold(Color::LightRed),
+                error: bold(Color::LightRed),
+                border: fg(Color::Gray),
+                tag: fg(Color::LightGreen),
+            },
+            Theme::Solarized => Self {
+                text: fg(Color::Reset),
+                text_dim: fg(Color::Rgb(0x58, 0x6e, 0x75)),
+                focus: fg(Color::Rgb(0x26, 0x8b, 0xd2)),
+                active: fg(Color::Rgb(0xb5, 0x89, 0x00)),
+                accent: fg(Color::Rgb(0xd3, 0x36, 0x82)),
+                stale: fg(Color::Rgb(0xcb, 0x4b, 0x16)),
+                error: fg(Color::Rgb(0xdc, 0x32, 0x2f)),
+                border: fg(Color::Rgb(0x58, 0x6e, 0x75)),
+                tag: fg(Color::Rgb(0x2a, 0xa1, 0x98)),
+            },
+            Theme::Monochrome => {
+                let with = |modifier| Style::default().add_modifier(modifier);
+
+                Self {
+                    text: Style::default(),
+                    text_dim: with(Modifier::DIM),
+                    focus: with(Modifier::BOLD),
+                    active: with(Modifier::BOLD | Modifier::UNDERLINED),
+                    accent: with(Modifier::REVERSED),
+                    stale: with(Modifier::ITALIC),
+                    error: with(Modifier::BOLD),
+                    border: Style::default(),
+                    tag: with(Modifier::ITALIC),
+                }
+            }
+        }
+    }
+
+    /// The palette for `theme` with `overrides` (role → color such as `cyan`,
+    /// `#2aa198` or `42`) applied. Invalid overrides are skipped and returned
+    /// as one report, so the valid ones still apply. With `no_color` the
+    /// monochrome theme is used and overrides are ignored.
+    pub fn resolve(
+        theme: Theme,
+        overrides: &BTreeMap<String, String>,
+        no_color: bool,
+    ) -> (Self, Option<miette::Report>) {
+        if no_color {
+            return (Self::theme(Theme::Monochrome), None);
+        }
+
+        let mut palette = Self::theme(theme);
+        let mut problems = Vec::new();
+
+        for (role, value) in overrides {
+            let Ok(color) = value.parse::<Color>() else {
+                problems.push(format!("unknown color '{value}' for {role}"));
+
+                continue;
+            };
+
+            let Some(style) = palette.role_mut(role) else {
+                problems.push(format!(
+                    "unknown color role '{role}' (expected one of {})",
+                    ROLES.join(", ")
+                ));
+
+                continue;
+            };
+
+            *style = style.fg(color);
+        }
+
+        let report = (!problems.is_empty()).then(|| miette::miette!("{}", problems.join("; ")));
+
+        (palette, report)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Style> {
+        let style = match role {
+            "text" => &mut self.text,
+            "text_dim" => &mut self.text_dim,
+            "focus" => &mut self.focus,
+            "active" => &mut self.active,
+            "accent" => &mut self.accent,
+            "stale" => &mut self.stale,
+            "error" => &mut self.error,
+            "border" => &mut self.border,
+            "tag" => &mut self.tag,
+            _ => return None,
+        };
+
+        Some(style)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::theme(Theme::Default)
+    }
+}
+
+/// Whether `NO_COLOR` asks for no colors (set and non-empty).
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
@@ -0,0 +1,199 @@
+//! Render TUI views once into a buffer and print them, for `mach week` and
+//! `mach backlog` in scripts and pipes.
+
+use chrono::NaiveDate;
+use crossterm::{
+    Command,
+    style::{Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use miette::IntoDiagnostic;
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Modifier, Style},
+};
+use tokio::runtime::Handle;
+use unicode_width::UnicodeWidthStr;
+
+use crate::service::Services;
+
+use super::App;
+use super::modes::UiMode;
+use super::state::WeekState;
+
+/// Tallest view we render before trimming unused rows.
+const MAX_HEIGHT: u16 = 500;
+
+/// Which screen to print.
+#[derive(Clone, Copy)]
+pub enum View {
+    /// The week containing the given date.
+    Week(NaiveDate),
+    Backlog,
+}
+
+/// Render `view` at `width` columns, returning it as text with ANSI colors
+/// unless `plain` is set.
+pub async fn render(
+    services: Services,
+    view: View,
+    width: u16,
+    plain: bool,
+) -> miette::Result<String> {
+    let handle = Handle::current();
+
+    let task = tokio::task::spawn_blocking(move || {
+        let mut app = App::new(services, handle);
+
+        app.prepare_snapshot(view)?;
+
+        let buffer = app.render_buffer(width, MAX_HEIGHT)?;
+        let height = content_height(&buffer, view);
+        let buffer = app.render_buffer(width, height)?;
+
+        Ok::<_, miette::Report>(buffer_to_string(&buffer, plain))
+    });
+
+    task.await.into_diagnostic()?
+}
+
+impl App {
+    fn prepare_snapshot(&mut self, view: View) -> miette::Result<()> {
+        if let View::Week(date) = view {
+            self.state = WeekState::new(date, self.week_pref);
+        }
+
+        self.refresh_board()?;
+
+        // Keep today's header highlighted but don't show a row cursor.
+        self.cursor.focus = self
+            .state
+            .column_index(self.services.today())
+            .unwrap_or(self.state.columns.len());
+
+        self.cursor.day_rows.fill(usize::MAX);
+        self.backlog_cursor.rows.fill(usize::MAX);
+
+        self.status_bar = false;
+
+        self.ui_mode = match view {
+            View::Week(_) => UiMode::Board,
+            View::Backlog => UiMode::Backlog,
+        };
+
+        Ok(())
+    }
+
+    pub(super) fn render_buffer(&mut self, width: u16, height: u16) -> miette::Result<Buffer> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).into_diagnostic()?;
+
+        terminal.draw(|frame| self.draw(frame)).into_diagnostic()?;
+
+        Ok(terminal.backend().buffer().clone())
+    }
+}
+
+/// Rows needed to show every todo, ignoring the blank tail of the columns.
+fn content_height(buffer: &Buffer, view: View) -> u16 {
+    let area = buffer.area;
+
+    let last_row = (0..area.height)
+        .rev()
+        .find(|&y| {
+            (0..area.width).any(|x| {
+                let symbol = buffer[(x, y)].symbol();
+
+                !matches!(symbol, " " | "│" | "─" | "╰" | "╯" | "└" | "┘" | "┴")
+            })
+        })
+        .unwrap_or(0);
+
+    match view {
+        // Leave room for the bottom border.
+        View::Backlog => (last_row + 2).max(3),
+        // Day headers and their underline are always drawn.
+        View::Week(_) => (last_row + 1).max(2),
+    }
+}
+
+pub(super) fn buffer_to_string(buffer: &Buffer, plain: bool) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+
+    for y in 0..area.height {
+        let mut line = String::new();
+        let mut current = Style::default();
+        let mut covered = 0;
+
+        for x in 0..area.width {
+            let cell = &buffer[(x, y)];
+
+            // A wide character already fills the cells after it; printing
+            // their placeholders would push the rest of the row right.
+            if covered > 0 {
+                covered -= 1;
+
+                continue;
+            }
+
+            if cell.skip {
+                continue;
+            }
+
+            if !plain {
+                let style = cell.style();
+
+                if style != current {
+                    write_style(&mut line, style);
+
+                    current = style;
+                }
+            }
+
+            line.push_str(cell.symbol());
+
+            covered = cell.symbol().width().saturating_sub(1);
+        }
+
+        if plain {
+            out.push_str(line.trim_end());
+        } else {
+            out.push_str(&line);
+
+            let _ = ResetColor.write_ansi(&mut out);
+            let _ = SetAttribute(Attribute::Reset).write_ansi(&mut out);
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+fn write_style(out: &mut String, style: Style) {
+    let _ = SetAttribute(Attribute::Reset).write_ansi(out);
+
+    if let Some(fg) = style.fg {
+        let _ = SetForegroundColor(fg.into()).write_ansi(out);
+    }
+
+    if let Some(bg) = style.bg {
+        let _ = SetBackgroundColor(bg.into()).write_ansi(out);
+    }
+
+    let modifiers = [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ];
+
+    for (modifier, attribute) in modifiers {
+        if style.add_modifier.contains(modifier) {
+            let _ = SetAttribute(attribute).write_ansi(out);
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use chrono::{Datelike, Duration as ChronoDuration, NaiveDate};
-use ratatui::style::{Modifier, Style};
-use ratatui::text::Line;
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
 use uuid::Uuid;
 
+use crate::dates::start_of_week;
 use crate::entity::todo;
-use crate::service::config::WeekStart;
+use crate::service::config::{TitleOverflow, WeekStart};
 
-use super::palette;
-
-pub const BACKLOG_COLUMNS: usize = 4;
+use super::palette::Palette;
+use super::text;
 
 pub struct WeekState {
     pub week_start: NaiveDate,
@@ -50,14 +50,17 @@ pub struct ColumnMeta {
 
 pub struct BoardData {
     pub days: Vec<Vec<TodoView>>,
-    pub backlog_columns: [Vec<TodoView>; BACKLOG_COLUMNS],
+    pub backlog_columns: Vec<Vec<TodoView>>,
+    /// Backlog column names, one per entry in `backlog_columns`.
+    pub backlog_titles: Vec<String>,
 }
 
 impl BoardData {
     pub fn new(num_days: usize) -> Self {
         Self {
             days: vec![Vec::new(); num_days],
-            backlog_columns: Default::default(),
+            backlog_columns: Vec::new(),
+            backlog_titles: Vec::new(),
         }
     }
 
@@ -85,18 +88,14 @@ impl BoardData {
         self.days.get(col)?.get(row).map(|todo| todo.id)
     }
 
-    pub fn set_backlog_column(&mut self, col: usize, todos: Vec<TodoView>) {
-        if col < BACKLOG_COLUMNS {
-            self.backlog_columns[col] = todos;
-        }
+    /// Replace the backlog with `columns`, named by `titles`.
+    pub fn set_backlog(&mut self, titles: Vec<String>, columns: Vec<Vec<TodoView>>) {
+        self.backlog_titles = titles;
+        self.backlog_columns = columns;
     }
 
     pub fn backlog_col_len(&self, col: usize) -> usize {
-        if col < BACKLOG_COLUMNS {
-            self.backlog_columns[col].len()
-        } else {
-            0
-        }
+        self.backlog_columns.get(col).map(Vec::len).unwrap_or(0)
     }
 
     pub fn backlog_todo_id_at(&self, col: usize, row: usize) -> Option<Uuid> {
@@ -149,33 +148,116 @@ pub struct TodoView {
     pub id: Uuid,
     pub title: String,
     pub status: String,
+    pub tags: Vec<String>,
+    pub rollover_count: u64,
 }
 
 impl TodoView {
-    pub fn to_line_with_prefix(&self, selected: bool) -> Line<'_> {
-        let text = if selected {
+    /// Render the todo into one or more lines that fit `width`, following the
+    /// configured overflow policy. Pending todos that rolled over more than
+    /// `stale_after` times get a `↻N` age marker.
+    pub fn to_lines(
+        &self,
+        selected: bool,
+        marked: bool,
+        width: u16,
+        overflow: TitleOverflow,
+        stale_after: u64,
+        palette: &Palette,
+    ) -> Vec<Line<'_>> {
+        let text = self.label(selected, marked, stale_after);
+
+        let style = if self.status == "done" {
+            palette
+                .text_dim
+                .add_modifier(Modifier::CROSSED_OUT | Modifier::DIM)
+        } else {
+            palette.text
+        };
+
+        let rows = match overflow {
+            TitleOverflow::Wrap => text::wrap(&text, width as usize),
+            TitleOverflow::Truncate => vec![text::truncate(&text, width as usize).0],
+        };
+
+        // Selected, marked and done todos keep one color throughout.
+        let plain = selected || marked || self.status == "done" || self.tags.is_empty();
+
+        rows.into_iter()
+            .map(|row| {
+                if plain {
+                    Line::from(row).style(style)
+                } else {
+                    Line::from(self.tag_spans(row, palette)).style(style)
+                }
+            })
+            .collect()
+    }
+
+    /// Split a rendered row so its `#tag` labels get the tag style.
+    fn tag_spans(&self, row: String, palette: &Palette) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        let mut plain = String::new();
+
+        for word in row.split_inclusive(' ') {
+            let label = word.trim_end().strip_prefix('#');
+
+            if label.is_some_and(|label| self.tags.iter().any(|tag| tag == label)) {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+
+                spans.push(Span::styled(word.to_string(), palette.tag));
+            } else {
+                plain.push_str(word);
+            }
+        }
+
+        if !plain.is_empty() {
+            spans.push(Span::raw(plain));
+        }
+
+        spans
+    }
+
+    pub fn is_stale(&self, stale_after: u64) -> bool {
+        self.status != "done" && self.rollover_count > stale_after
+    }
+
+    /// The text drawn for the todo: a selection or mark prefix, the title,
+    /// the stale marker and `#tag` labels.
+    fn label(&self, selected: bool, marked: bool, stale_after: u64) -> String {
+        let mut text = if selected {
             format!("› {}", self.title)
+        } else if marked {
+            format!("◆ {}", self.title)
         } else {
             self.title.clone()
         };
 
-        let mut line = Line::from(text);
+        if self.is_stale(stale_after) {
+            text.push_str(&format!(" ↻{}", self.rollover_count));
+        }
 
-        if self.status == "done" {
-            line.style = Style::default()
-                .fg(palette::TEXT_DIM)
-                .add_modifier(Modifier::CROSSED_OUT | Modifier::DIM);
-        } else {
-            line.style = Style::default().fg(palette::TEXT);
+        for tag in &self.tags {
+            text.push_str(" #");
+            text.push_str(tag);
         }
 
-        line
+        text
+    }
+
+    /// Whether the todo would be cut when rendered in truncate mode.
+    pub fn is_truncated(&self, selected: bool, marked: bool, width: u16, stale_after: u64) -> bool {
+        text::truncate(&self.label(selected, marked, stale_after), width as usize).1
     }
 }
 
 impl From<todo::Model> for TodoView {
     fn from(model: todo::Model) -> Self {
         Self {
+            tags: model.tags(),
+            rollover_count: model.rollover_count(),
             id: model.id,
             title: model.title,
             status: model.status,
@@ -213,14 +295,3 @@ fn weekday_label(day: chrono::Weekday) -> &'static str {
         chrono::Weekday::Sun => "Sun",
     }
 }
-
-pub fn start_of_week(date: NaiveDate, preference: WeekStart) -> NaiveDate {
-    let weekday = date.weekday();
-
-    let offset = match preference {
-        WeekStart::Sunday => weekday.num_days_from_sunday() as i64,
-        WeekStart::Monday => weekday.num_days_from_monday() as i64,
-    };
-
-    date - ChronoDuration::days(offset)
-}
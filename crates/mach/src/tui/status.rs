@@ -0,0 +1,86 @@
+//! The one-line status bar under the board and backlog: transient
+//! confirmations and errors, pending key prefixes and what's on screen.
+
+use std::time::{Duration, Instant};
+
+/// How long a confirmation stays up.
+const INFO_TTL: Duration = Duration::from_secs(4);
+/// Errors stay longer so they can be read.
+const ERROR_TTL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub kind: MessageKind,
+    pub text: String,
+    shown_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct StatusLine {
+    message: Option<Message>,
+}
+
+impl StatusLine {
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.show(MessageKind::Info, text.into());
+    }
+
+    /// Show `err` with its causes, e.g. `invalid date: ...`.
+    pub fn error(&mut self, err: &miette::Report) {
+        let text = err
+            .chain()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        self.show(MessageKind::Error, text);
+    }
+
+    pub fn clear(&mut self) {
+        self.message = None;
+    }
+
+    /// The message to display, unless it has expired.
+    pub fn current(&self) -> Option<&Message> {
+        self.message.as_ref().filter(|message| {
+            let ttl = match message.kind {
+                MessageKind::Info => INFO_TTL,
+                MessageKind::Error => ERROR_TTL,
+            };
+
+            message.shown_at.elapsed() < ttl
+        })
+    }
+
+    /// Whether an error is on screen. `Esc` dismisses it before doing
+    /// anything else; confirmations just time out.
+    pub fn has_error(&self) -> bool {
+        self.current()
+            .is_some_and(|message| message.kind == MessageKind::Error)
+    }
+
+    fn show(&mut self, kind: MessageKind, text: String) {
+        self.message = Some(Message {
+            kind,
+            text,
+            shown_at: Instant::now(),
+        });
+    }
+}
+
+/// Route a failed action to the status bar instead of dropping it.
+pub trait ReportExt<T> {
+    fn report(self, status: &mut StatusLine) -> Option<T>;
+}
+
+impl<T> ReportExt<T> for miette::Result<T> {
+    fn report(self, status: &mut StatusLine) -> Option<T> {
+        self.map_err(|err| status.error(&err)).ok()
+    }
+}
@@ -1,38 +1,75 @@
 use std::io;
 
 use crossterm::{
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use miette::{Context, IntoDiagnostic};
 use ratatui::{Terminal, backend::CrosstermBackend};
 
-pub fn setup_terminal() -> miette::Result<Terminal<CrosstermBackend<io::Stdout>>> {
+pub type Tui = Terminal<CrosstermBackend<io::Stdout>>;
+
+pub fn setup_terminal() -> miette::Result<Tui> {
+    enter_terminal()?;
+
+    let backend = CrosstermBackend::new(io::stdout());
+
+    Terminal::new(backend)
+        .into_diagnostic()
+        .wrap_err("failed to initialize terminal")
+}
+
+/// Hand the terminal back to the shell so another program (e.g. `$EDITOR`)
+/// can use it. Pair with [`resume_terminal`].
+pub fn suspend_terminal() {
+    restore_terminal();
+}
+
+/// Re-enter the TUI after [`suspend_terminal`], forcing a full redraw.
+pub fn resume_terminal(terminal: &mut Tui) -> miette::Result<()> {
+    enter_terminal()?;
+
+    terminal
+        .clear()
+        .into_diagnostic()
+        .wrap_err("failed to clear terminal")
+}
+
+fn enter_terminal() -> miette::Result<()> {
     enable_raw_mode()
         .into_diagnostic()
         .wrap_err("failed to enable raw mode")?;
 
     let mut stdout = io::stdout();
 
-    execute!(stdout, EnterAlternateScreen)
-        .into_diagnostic()
-        .wrap_err("failed to enter alternate screen")?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )
+    .into_diagnostic()
+    .wrap_err("failed to enter alternate screen")
+}
 
-    let backend = CrosstermBackend::new(stdout);
+fn restore_terminal() {
+    let _ = disable_raw_mode();
 
-    Terminal::new(backend)
-        .into_diagnostic()
-        .wrap_err("failed to initialize terminal")
+    let mut stdout = io::stdout();
+
+    let _ = execute!(
+        stdout,
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    );
 }
 
 pub struct TerminalGuard;
 
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
-        let _ = disable_raw_mode();
-
-        let mut stdout = io::stdout();
-
-        let _ = execute!(stdout, LeaveAlternateScreen);
+        restore_terminal();
     }
 }
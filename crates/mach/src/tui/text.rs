@@ -0,0 +1,85 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ELLIPSIS: char = '…';
+
+/// Split `text` into lines no wider than `width`, breaking on whitespace when
+/// possible and falling back to hard breaks for words longer than a line.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![String::new()];
+    }
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for word in text.split_whitespace() {
+        let word_width = word.width();
+        let gap = usize::from(current_width > 0);
+
+        if current_width + gap + word_width <= width {
+            if gap > 0 {
+                current.push(' ');
+            }
+
+            current.push_str(word);
+            current_width += gap + word_width;
+
+            continue;
+        }
+
+        if current_width > 0 {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+
+        for ch in word.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+
+            if current_width + ch_width > width && current_width > 0 {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+
+            current.push(ch);
+            current_width += ch_width;
+        }
+    }
+
+    if current_width > 0 || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Cut `text` to at most `width` columns, ending in an ellipsis when anything
+/// was dropped. Returns the rendered text and whether it was truncated.
+pub fn truncate(text: &str, width: usize) -> (String, bool) {
+    if text.width() <= width {
+        return (text.to_string(), false);
+    }
+
+    if width == 0 {
+        return (String::new(), true);
+    }
+
+    let budget = width - 1;
+    let mut out = String::new();
+    let mut used = 0;
+
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+
+        if used + ch_width > budget {
+            break;
+        }
+
+        out.push(ch);
+        used += ch_width;
+    }
+
+    out.push(ELLIPSIS);
+
+    (out, true)
+}
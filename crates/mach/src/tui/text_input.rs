@@ -0,0 +1,255 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Editable text buffer with a cursor, shared by every text field in the TUI.
+///
+/// The cursor is a byte offset that always sits on a char boundary.
+#[derive(Clone, Default)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    multiline: bool,
+}
+
+impl TextInput {
+    /// Single-line input with the cursor placed at the end of `value`.
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+
+        Self {
+            cursor: value.len(),
+            value,
+            multiline: false,
+        }
+    }
+
+    /// Multi-line input; `Ctrl+j` inserts newlines and `Up`/`Down` move between lines.
+    pub fn multiline(value: impl Into<String>) -> Self {
+        Self {
+            multiline: true,
+            ..Self::new(value)
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn into_value(self) -> String {
+        self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Replace the contents, moving the cursor to the end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    /// Insert text at the cursor. Single-line inputs flatten newlines to spaces.
+    pub fn insert_str(&mut self, text: &str) {
+        let text = if self.multiline {
+            text.replace("\r\n", "\n").replace('\r', "\n")
+        } else {
+            text.replace("\r\n", " ").replace(['\r', '\n'], " ")
+        };
+
+        self.value.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    /// Apply an editing key. Returns `false` when the key isn't an editing
+    /// command so the caller can handle it (submit, cancel, history, ...).
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('j') if ctrl && self.multiline => self.insert_char('\n'),
+            KeyCode::Char('a') if ctrl => self.cursor = self.line_start(),
+            KeyCode::Char('e') if ctrl => self.cursor = self.line_end(),
+            KeyCode::Char('b') if ctrl => self.move_left(),
+            KeyCode::Char('f') if ctrl => self.move_right(),
+            KeyCode::Char('b') if alt => self.cursor = self.word_left(),
+            KeyCode::Char('f') if alt => self.cursor = self.word_right(),
+            KeyCode::Char('w') if ctrl => self.delete_to(self.word_left()),
+            KeyCode::Char('u') if ctrl => self.delete_to(self.line_start()),
+            KeyCode::Char('k') if ctrl => self.delete_to(self.line_end()),
+            KeyCode::Char('d') if ctrl => self.delete_to(self.next_boundary()),
+            KeyCode::Char(_) if ctrl || alt => return false,
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Backspace if ctrl || alt => self.delete_to(self.word_left()),
+            KeyCode::Backspace => self.delete_to(self.prev_boundary()),
+            KeyCode::Delete => self.delete_to(self.next_boundary()),
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_left(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_right(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            KeyCode::Up if self.multiline => self.move_line(-1),
+            KeyCode::Down if self.multiline => self.move_line(1),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Zero-based line and column (in chars) of the cursor.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let before = &self.value[..self.cursor];
+        let line = before.matches('\n').count();
+        let col = before[self.line_start()..].chars().count();
+
+        (line, col)
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.value.split('\n').count()
+    }
+
+    /// Render every line with the cursor cell shown in reverse video.
+    pub fn render(&self, style: Style) -> Vec<Line<'static>> {
+        let (cursor_line, cursor_col) = self.cursor_position();
+        let cursor_style = style.add_modifier(Modifier::REVERSED);
+
+        self.value
+            .split('\n')
+            .enumerate()
+            .map(|(idx, text)| {
+                if idx != cursor_line {
+                    return Line::from(Span::styled(text.to_string(), style));
+                }
+
+                let split = text
+                    .char_indices()
+                    .nth(cursor_col)
+                    .map(|(i, _)| i)
+                    .unwrap_or(text.len());
+
+                let (before, rest) = text.split_at(split);
+                let mut chars = rest.chars();
+                let under = chars.next().map(String::from).unwrap_or_else(|| " ".into());
+
+                Line::from(vec![
+                    Span::styled(before.to_string(), style),
+                    Span::styled(under, cursor_style),
+                    Span::styled(chars.as_str().to_string(), style),
+                ])
+            })
+            .collect()
+    }
+
+    fn insert_char(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Delete the text between the cursor and `target`, in either direction.
+    fn delete_to(&mut self, target: usize) {
+        let (start, end) = if target < self.cursor {
+            (target, self.cursor)
+        } else {
+            (self.cursor, target)
+        };
+
+        self.value.replace_range(start..end, "");
+        self.cursor = start;
+    }
+
+    fn move_left(&mut self) {
+        self.cursor = self.prev_boundary();
+    }
+
+    fn move_right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map(|c| self.cursor + c.len_utf8())
+            .unwrap_or(self.cursor)
+    }
+
+    fn line_start(&self) -> usize {
+        self.value[..self.cursor]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0)
+    }
+
+    fn line_end(&self) -> usize {
+        self.value[self.cursor..]
+            .find('\n')
+            .map(|i| self.cursor + i)
+            .unwrap_or(self.value.len())
+    }
+
+    /// Start of the word before the cursor, skipping any whitespace first.
+    fn word_left(&self) -> usize {
+        let before = &self.value[..self.cursor];
+        let trimmed = before.trim_end_matches(|c: char| c.is_whitespace());
+
+        trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0)
+    }
+
+    /// End of the word after the cursor, skipping any whitespace first.
+    fn word_right(&self) -> usize {
+        let after = &self.value[self.cursor..];
+        let skipped = after.len() - after.trim_start_matches(|c: char| c.is_whitespace()).len();
+
+        after[skipped..]
+            .char_indices()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(i, _)| self.cursor + skipped + i)
+            .unwrap_or(self.value.len())
+    }
+
+    fn move_line(&mut self, delta: isize) {
+        let (line, col) = self.cursor_position();
+        let target = line as isize + delta;
+
+        if target < 0 || target as usize >= self.line_count() {
+            return;
+        }
+
+        let mut offset = 0;
+
+        for (idx, text) in self.value.split('\n').enumerate() {
+            if idx == target as usize {
+                let within = text
+                    .char_indices()
+                    .nth(col)
+                    .map(|(i, _)| i)
+                    .unwrap_or(text.len());
+
+                self.cursor = offset + within;
+
+                return;
+            }
+
+            offset += text.len() + 1;
+        }
+    }
+}
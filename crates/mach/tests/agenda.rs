@@ -0,0 +1,132 @@
+mod common;
+
+use machich::service::agenda::{Agenda, AgendaItem};
+use machich::service::config::WeekStart;
+use machich::service::todo::{ListScope, MovePlacement};
+
+use common::{Fixture, date, todo};
+
+fn titles(items: &[AgendaItem]) -> Vec<&str> {
+    items.iter().map(|item| item.title.as_str()).collect()
+}
+
+async fn agenda(fx: &Fixture, week_start: WeekStart) -> Agenda {
+    fx.services
+        .todos
+        .agenda(fx.today(), week_start)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn agenda_sections() {
+    // Wednesday 2025-01-15.
+    let fx = Fixture::new().await;
+
+    todo("Today").insert(&fx).await;
+    todo("Finished today").done().insert(&fx).await;
+    todo("Friday").on(date(2025, 1, 17)).insert(&fx).await;
+    todo("Done Friday")
+        .on(date(2025, 1, 17))
+        .done()
+        .insert(&fx)
+        .await;
+    todo("Next week").on(date(2025, 1, 21)).insert(&fx).await;
+    todo("Shipped")
+        .on(date(2025, 1, 14))
+        .done()
+        .insert(&fx)
+        .await;
+    todo("Monday")
+        .on(date(2025, 1, 13))
+        .done()
+        .insert(&fx)
+        .await;
+    todo("Someday").backlog().insert(&fx).await;
+
+    let agenda = agenda(&fx, WeekStart::Monday).await;
+
+    assert_eq!(agenda.date, date(2025, 1, 15));
+    assert_eq!(titles(&agenda.today), ["Today", "Finished today"]);
+    assert!(agenda.rolled_over.is_empty());
+    assert_eq!(titles(&agenda.upcoming), ["Friday"]);
+    assert_eq!(titles(&agenda.done_yesterday), ["Shipped"]);
+}
+
+#[tokio::test]
+async fn agenda_reports_rolled_over_todos_and_slip() {
+    let fx = Fixture::new().await;
+
+    todo("Slipping").on(date(2025, 1, 13)).insert(&fx).await;
+    fx.services.todos.rollover_to(fx.today()).await.unwrap();
+    todo("Fresh").insert(&fx).await;
+    todo("Rolled but done")
+        .on(date(2025, 1, 14))
+        .insert(&fx)
+        .await;
+    let rolled = fx.services.todos.rollover_to(fx.today()).await.unwrap();
+    fx.services
+        .todos
+        .mark_done(rolled[0], fx.today())
+        .await
+        .unwrap();
+
+    let agenda = agenda(&fx, WeekStart::Monday).await;
+
+    assert_eq!(
+        titles(&agenda.today),
+        ["Fresh", "Slipping", "Rolled but done"]
+    );
+    assert_eq!(titles(&agenda.rolled_over), ["Slipping"]);
+
+    let slipping = &agenda.rolled_over[0];
+
+    assert_eq!(slipping.rollover_count, 1);
+    assert_eq!(slipping.slipped_days, Some(2));
+    assert_eq!(agenda.today[0].slipped_days, None);
+}
+
+#[tokio::test]
+async fn agenda_leaves_out_todos_that_rolled_over_on_an_earlier_day() {
+    let fx = Fixture::new().await;
+    let todos = &fx.services.todos;
+
+    let old = todo("Slipped last week")
+        .on(date(2025, 1, 6))
+        .insert(&fx)
+        .await;
+    todos.rollover_to(date(2025, 1, 8)).await.unwrap();
+    todos
+        .move_to_scope(old.id, ListScope::Day(fx.today()), MovePlacement::Top)
+        .await
+        .unwrap();
+
+    todo("Slipped today")
+        .on(date(2025, 1, 14))
+        .insert(&fx)
+        .await;
+    todos.rollover_to(fx.today()).await.unwrap();
+
+    let agenda = agenda(&fx, WeekStart::Monday).await;
+
+    assert_eq!(
+        titles(&agenda.today),
+        ["Slipped last week", "Slipped today"]
+    );
+    assert_eq!(agenda.today[0].rollover_count, 1);
+    assert_eq!(titles(&agenda.rolled_over), ["Slipped today"]);
+}
+
+#[tokio::test]
+async fn agenda_upcoming_stops_at_the_end_of_the_week() {
+    // Saturday 2025-01-18: the last day of a Sunday week.
+    let fx = Fixture::at(date(2025, 1, 18)).await;
+
+    todo("Sunday").on(date(2025, 1, 19)).insert(&fx).await;
+
+    let sunday_weeks = agenda(&fx, WeekStart::Sunday).await;
+    assert!(sunday_weeks.upcoming.is_empty());
+
+    let monday_weeks = agenda(&fx, WeekStart::Monday).await;
+    assert_eq!(titles(&monday_weeks.upcoming), ["Sunday"]);
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use machich::service::backlog::{DEFAULT_COLUMNS, MAX_COLUMNS, RemovedColumn};
+use machich::service::todo::{PlacementQuery, TodoQuery};
+
+use common::{Fixture, todo};
+
+/// Titles in one backlog column, top to bottom.
+async fn column(fx: &Fixture, col: i64) -> Vec<String> {
+    let query = TodoQuery::new().placement(PlacementQuery::Backlog(Some(col)));
+
+    fx.services
+        .todos
+        .query(&query)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|todo| todo.title)
+        .collect()
+}
+
+#[tokio::test]
+async fn columns_start_with_the_defaults() {
+    let fx = Fixture::new().await;
+
+    assert_eq!(
+        fx.services.backlog.columns().await.unwrap(),
+        DEFAULT_COLUMNS
+    );
+}
+
+#[tokio::test]
+async fn add_and_rename_check_names() {
+    let fx = Fixture::new().await;
+    let backlog = &fx.services.backlog;
+
+    assert_eq!(backlog.add_column("  Reading ").await.unwrap(), 4);
+    backlog.rename_column(0, "Someday").await.unwrap();
+
+    assert_eq!(
+        backlog.columns().await.unwrap(),
+        ["Someday", "Next", "Waiting", "Later", "Reading"]
+    );
+
+    assert!(backlog.add_column("next").await.is_err());
+    assert!(backlog.add_column("   ").await.is_err());
+    assert!(backlog.rename_column(1, "Waiting").await.is_err());
+    assert!(backlog.rename_column(9, "Nine").await.is_err());
+
+    // Renaming a column to itself (in another case) is fine.
+    backlog.rename_column(1, "NEXT").await.unwrap();
+
+    for n in backlog.columns().await.unwrap().len()..MAX_COLUMNS {
+        backlog.add_column(&format!("Extra {n}")).await.unwrap();
+    }
+
+    assert!(backlog.add_column("One too many").await.is_err());
+}
+
+#[tokio::test]
+async fn resolve_column_by_name_or_number() {
+    let fx = Fixture::new().await;
+    let backlog = &fx.services.backlog;
+
+    assert_eq!(backlog.resolve_column("waiting").await.unwrap(), 2);
+    assert_eq!(backlog.resolve_column("4").await.unwrap(), 3);
+    assert!(backlog.resolve_column("0").await.is_err());
+    assert!(backlog.resolve_column("5").await.is_err());
+    assert!(backlog.resolve_column("Inbox").await.is_err());
+}
+
+#[tokio::test]
+async fn move_column_takes_its_todos_along() {
+    let fx = Fixture::new().await;
+    let backlog = &fx.services.backlog;
+
+    todo("idea").column(0).insert(&fx).await;
+    todo("next").column(1).insert(&fx).await;
+    todo("later").column(3).insert(&fx).await;
+
+    assert_eq!(backlog.move_column(0, 2).await.unwrap(), 2);
+
+    assert_eq!(
+        backlog.columns().await.unwrap(),
+        ["Next", "Waiting", "Ideas", "Later"]
+    );
+    assert_eq!(column(&fx, 0).await, ["next"]);
+    assert!(column(&fx, 1).await.is_empty());
+    assert_eq!(column(&fx, 2).await, ["idea"]);
+    assert_eq!(column(&fx, 3).await, ["later"]);
+}
+
+#[tokio::test]
+async fn remove_column_moves_todos_to_the_neighbour() {
+    let fx = Fixture::new().await;
+    let backlog = &fx.services.backlog;
+
+    todo("a").column(0).insert(&fx).await;
+    todo("b").column(1).insert(&fx).await;
+    todo("d").column(3).insert(&fx).await;
+
+    let removed = backlog.remove_column(1).await.unwrap();
+
+    assert_eq!(
+        removed,
+        RemovedColumn {
+            name: "Next".into(),
+            into: "Ideas".into(),
+            moved: 1,
+        }
+    );
+
+    assert_eq!(
+        backlog.columns().await.unwrap(),
+        ["Ideas", "Waiting", "Later"]
+    );
+
+    // Moved todos go below the ones already there.
+    assert_eq!(column(&fx, 0).await, ["a", "b"]);
+    assert!(column(&fx, 1).await.is_empty());
+    assert_eq!(column(&fx, 2).await, ["d"]);
+
+    // The first column hands its todos to the right.
+    let removed = backlog.remove_column(0).await.unwrap();
+
+    assert_eq!(removed.into, "Waiting");
+    assert_eq!(column(&fx, 0).await, ["a", "b"]);
+    assert_eq!(column(&fx, 1).await, ["d"]);
+
+    backlog.remove_column(0).await.unwrap();
+
+    assert_eq!(column(&fx, 0).await, ["d", "a", "b"]);
+    assert!(backlog.remove_column(0).await.is_err());
+}
+
+#[tokio::test]
+async fn removing_the_rightmost_column_takes_strays_along() {
+    let fx = Fixture::new().await;
+    let backlog = &fx.services.backlog;
+
+    todo("waiting").column(2).insert(&fx).await;
+    todo("later").column(3).insert(&fx).await;
+    todo("stray").column(6).insert(&fx).await;
+
+    let removed = backlog.remove_column(3).await.unwrap();
+
+    assert_eq!(removed.into, "Waiting");
+    assert_eq!(removed.moved, 2);
+    assert_eq!(column(&fx, 2).await, ["waiting", "later", "stray"]);
+    assert!(column(&fx, 6).await.is_empty());
+}
+
+#[tokio::test]
+async fn move_many_to_backlog_appends_to_the_column_in_order() {
+    let fx = Fixture::new().await;
+    let today = fx.today();
+
+    todo("Waiting already").column(2).insert(&fx).await;
+    let first = todo("first").on(today).insert(&fx).await;
+    let second = todo("second").insert(&fx).await;
+
+    fx.services
+        .todos
+        .move_many_to_backlog(&[first.id, second.id], Some(2))
+        .await
+        .unwrap();
+
+    assert_eq!(column(&fx, 2).await, ["Waiting already", "first", "second"]);
+    assert!(fx.titles_on(today).await.is_empty());
+}
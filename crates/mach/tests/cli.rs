@@ -0,0 +1,218 @@
+//! Run the `mach` binary against a fresh data directory, with today pinned
+//! to Wed 2025-01-15.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use uuid::Uuid;
+
+/// A home directory of its own, removed when dropped.
+struct Mach {
+    home: PathBuf,
+}
+
+impl Mach {
+    fn new() -> Self {
+        let home = std::env::temp_dir().join(format!("mach-cli-{}", Uuid::new_v4()));
+
+        std::fs::create_dir_all(&home).unwrap();
+
+        Self { home }
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_mach"));
+
+        command
+            .args(["--today", "2025-01-15"])
+            .args(args)
+            .env("HOME", &self.home)
+            .env("XDG_DATA_HOME", self.home.join("data"))
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::null());
+
+        command
+    }
+
+    fn output(&self, args: &[&str], stdin: Option<&str>) -> Output {
+        let mut command = self.command(args);
+
+        if stdin.is_some() {
+            command.stdin(Stdio::piped());
+        }
+
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        if let Some(input) = stdin {
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(input.as_bytes())
+                .unwrap();
+        }
+
+        child.wait_with_output().unwrap()
+    }
+
+    /// Stdout of a run that must succeed.
+    fn run(&self, args: &[&str], stdin: Option<&str>) -> String {
+        let output = self.output(args, stdin);
+
+        assert!(
+            output.status.success(),
+            "mach {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    /// Titles printed by `mach list`, top to bottom.
+    fn list(&self, args: &[&str]) -> Vec<String> {
+        let mut list = vec!["list"];
+
+        list.extend(args);
+
+        self.run(&list, None)
+            .lines()
+            .skip(2)
+            .filter_map(|line| line.get(31..))
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+impl Drop for Mach {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.home);
+    }
+}
+
+#[test]
+fn add_reads_titles_from_stdin() {
+    let mach = Mach::new();
+
+    mach.run(&["add", "Existing"], None);
+
+    let out = mach.run(&["add"], Some("Buy milk\n\n  Call mum  \n"));
+
+    assert_eq!(
+        out.lines().collect::<Vec<_>>(),
+        [
+            "Added todo 'Buy milk' -> 2025-01-15",
+            "Added todo 'Call mum' -> 2025-01-15",
+        ]
+    );
+
+    // Piped todos keep their order on top of the day.
+    assert_eq!(mach.list(&[]), ["Buy milk", "Call mum", "Existing"]);
+
+    mach.run(&["add", "--bottom"], Some("Last one\nReally last\n"));
+
+    assert_eq!(
+        mach.list(&[]),
+        [
+            "Buy milk",
+            "Call mum",
+            "Existing",
+            "Last one",
+            "Really last"
+        ]
+    );
+
+    let output = mach.output(&["add"], Some("\n  \n"));
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no titles on stdin"));
+}
+
+#[test]
+fn add_reads_notes_from_a_file() {
+    let mach = Mach::new();
+    let notes = mach.home.join("notes.md");
+
+    std::fs::write(&notes, "- [ ] check the faucet\n\n").unwrap();
+
+    mach.run(
+        &[
+            "add",
+            "--notes-file",
+            notes.to_str().unwrap(),
+            "Call plumber",
+        ],
+        None,
+    );
+    mach.run(&["add", "No notes"], None);
+
+    assert_eq!(mach.list(&["--search", "faucet"]), ["Call plumber"]);
+
+    let missing = mach.home.join("missing.md");
+    let output = mach.output(
+        &["add", "--notes-file", missing.to_str().unwrap(), "Lost"],
+        None,
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed to read notes"));
+    assert_eq!(mach.list(&["--search", "Lost"]), Vec::<String>::new());
+}
+
+#[test]
+fn add_schedules_on_another_day() {
+    let mach = Mach::new();
+
+    let out = mach.run(&["add", "--on", "fri", "Pay rent"], None);
+
+    assert_eq!(out.trim(), "Added todo 'Pay rent' -> 2025-01-17");
+    assert_eq!(mach.list(&["due:2025-01-17"]), ["Pay rent"]);
+    assert_eq!(mach.run(&["list"], None).trim(), "No todos found.");
+
+    let out = mach.run(&["add", "--column", "2", "Read paper"], None);
+
+    assert_eq!(out.trim(), "Added todo 'Read paper' -> Someday / Next");
+    assert_eq!(mach.list(&["col:2"]), ["Read paper"]);
+
+    let output = mach.output(&["add", "--on", "whenever", "Nope"], None);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unrecognized date 'whenever'"));
+}
+
+#[test]
+fn bulk_dry_run_rejects_what_the_real_run_rejects() {
+    let mach = Mach::new();
+
+    mach.run(&["add", "Pay rent"], None);
+
+    for action in [
+        &["move", "garbage"][..],
+        &["backlog", "--column", "9"],
+        &["tag", "+"],
+    ] {
+        for dry_run in [true, false] {
+            let mut args = vec!["bulk", "--title", "rent"];
+
+            if dry_run {
+                args.push("--dry-run");
+            }
+
+            args.extend(action);
+
+            assert!(!mach.output(&args, None).status.success(), "{args:?}");
+        }
+    }
+
+    let out = mach.run(
+        &["bulk", "--title", "rent", "--dry-run", "move", "fri"],
+        None,
+    );
+
+    assert!(out.contains("Would move to fri 1 todo(s)."));
+    assert_eq!(mach.list(&[]), ["Pay rent"]);
+}
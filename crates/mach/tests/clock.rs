@@ -0,0 +1,26 @@
+mod common;
+
+use machich::service::clock::{Clock, FixedClock, TimeZonePref};
+
+use common::date;
+
+#[test]
+fn fixed_clock_lands_on_the_date_in_far_zones() {
+    let day = date(2025, 1, 15);
+
+    for name in [
+        "Pacific/Kiritimati",
+        "Pacific/Auckland",
+        "Pacific/Pago_Pago",
+        "UTC",
+    ] {
+        let zone: TimeZonePref = name.parse().unwrap();
+        let clock = FixedClock::on(day, zone);
+
+        assert_eq!(zone.date_of(clock.now()), day, "{name}");
+    }
+
+    let local = FixedClock::on(day, TimeZonePref::Local);
+
+    assert_eq!(TimeZonePref::Local.date_of(local.now()), day);
+}
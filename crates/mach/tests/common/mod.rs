@@ -0,0 +1,158 @@
+//! Shared fixture for service and TUI integration tests.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate};
+use machich::{
+    entity::todo,
+    service::{
+        Services,
+        clock::{FixedClock, TimeZonePref},
+        connection::init_memory_database,
+        todo::{ListOptions, ListScope, Slot},
+    },
+};
+
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// Services over a fresh in-memory database with a pinned clock.
+pub struct Fixture {
+    pub services: Services,
+    pub clock: Arc<FixedClock>,
+}
+
+impl Fixture {
+    /// A fixture whose "today" is Wednesday 2025-01-15.
+    pub async fn new() -> Self {
+        Self::at(date(2025, 1, 15)).await
+    }
+
+    pub async fn at(today: NaiveDate) -> Self {
+        let conn = init_memory_database().await.unwrap();
+        let clock = Arc::new(FixedClock::on(today, TimeZonePref::Local));
+
+        let services = Services::open(conn, clock.clone()).await.unwrap();
+
+        Self { services, clock }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.services.today()
+    }
+
+    /// Move the clock to `days` later and start that day, as the app would
+    /// after midnight.
+    pub async fn advance_days(&mut self, days: i64) {
+        self.clock.advance(Duration::days(days));
+
+        let today = self.services.current_date();
+
+        self.services.start_day(today).await.unwrap();
+    }
+
+    /// Titles in `scope` in display order, including done todos.
+    pub async fn titles(&self, scope: ListScope) -> Vec<String> {
+        self.services
+            .todos
+            .list(ListOptions {
+                scope,
+                include_done: true,
+            })
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|todo| todo.title)
+            .collect()
+    }
+
+    pub async fn titles_on(&self, date: NaiveDate) -> Vec<String> {
+        self.titles(ListScope::Day(date)).await
+    }
+}
+
+pub fn todo(title: &str) -> TodoBuilder {
+    TodoBuilder {
+        title: title.to_string(),
+        scheduled_for: None,
+        backlog: false,
+        backlog_column: None,
+        notes: None,
+        done: false,
+        tags: Vec::new(),
+    }
+}
+
+/// Creates todos through the service layer, the way the app does.
+pub struct TodoBuilder {
+    title: String,
+    scheduled_for: Option<NaiveDate>,
+    backlog: bool,
+    backlog_column: Option<i64>,
+    notes: Option<String>,
+    done: bool,
+    tags: Vec<String>,
+}
+
+impl TodoBuilder {
+    /// Schedule for `date` (default: the fixture's today).
+    pub fn on(mut self, date: NaiveDate) -> Self {
+        self.scheduled_for = Some(date);
+        self
+    }
+
+    pub fn backlog(mut self) -> Self {
+        self.backlog = true;
+        self
+    }
+
+    pub fn column(mut self, column: i64) -> Self {
+        self.backlog = true;
+        self.backlog_column = Some(column);
+        self
+    }
+
+    pub fn notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.to_string());
+        self
+    }
+
+    pub fn done(mut self) -> Self {
+        self.done = true;
+        self
+    }
+
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    pub async fn insert(self, fx: &Fixture) -> todo::Model {
+        let todos = &fx.services.todos;
+
+        let scheduled_for = if self.backlog {
+            None
+        } else {
+            Some(self.scheduled_for.unwrap_or(fx.today()))
+        };
+
+        let column = self.backlog_column.unwrap_or(0);
+        let mut model = todos
+            .transaction(async |txn| {
+                txn.add(self.title, scheduled_for, column, self.notes, Slot::Top)
+                    .await
+            })
+            .await
+            .unwrap();
+
+        todos.retag_many(&[model.id], &self.tags).await.unwrap();
+
+        if self.done {
+            model = todos.mark_done(model.id, fx.today()).await.unwrap();
+        }
+
+        todos.get(model.id).await.unwrap()
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use machich::service::config::{Theme, WeekStart};
+use serde_json::json;
+
+use common::Fixture;
+
+#[tokio::test]
+async fn unset_settings_fall_back_to_their_defaults() {
+    let fx = Fixture::new().await;
+    let config = &fx.services.config;
+
+    assert_eq!(
+        config.get("week_start").await.unwrap(),
+        Some(json!("sunday"))
+    );
+    assert_eq!(config.get("stale_after").await.unwrap(), Some(json!(3)));
+    assert_eq!(config.get("color.focus").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn set_validates_and_normalizes_input() {
+    let fx = Fixture::new().await;
+    let config = &fx.services.config;
+
+    assert_eq!(
+        config.set("week_start", "Monday").await.unwrap(),
+        json!("monday")
+    );
+    assert_eq!(
+        config.set("auto_rollover", "off").await.unwrap(),
+        json!(false)
+    );
+    assert_eq!(config.set("stale_after", "7").await.unwrap(), json!(7));
+    assert_eq!(
+        config.set("color.tag", "#2AA198").await.unwrap(),
+        json!("#2aa198")
+    );
+    assert_eq!(
+        config.set("color.focus", "Light-Blue").await.unwrap(),
+        json!("light-blue")
+    );
+    assert_eq!(config.set("color.text", "42").await.unwrap(), json!("42"));
+
+    assert_eq!(config.load_week_start().await.unwrap(), WeekStart::Monday);
+    assert_eq!(config.load_stale_after().await.unwrap(), 7);
+
+    for (key, input) in [
+        ("week_start", "tuesday"),
+        ("auto_rollover", "maybe"),
+        ("stale_after", "1000"),
+        ("timezone", "Mars/Olympus"),
+        ("color.tag", "blurple"),
+        ("color.tag", "#2aa19"),
+        ("color.tag", "256"),
+        ("no_such_setting", "1"),
+    ] {
+        assert!(config.set(key, input).await.is_err(), "{key} = {input}");
+    }
+
+    // Rejected input leaves the stored value alone.
+    assert_eq!(config.get("stale_after").await.unwrap(), Some(json!(7)));
+}
+
+#[tokio::test]
+async fn reset_returns_to_the_default() {
+    let fx = Fixture::new().await;
+    let config = &fx.services.config;
+
+    config.set("theme", "solarized").await.unwrap();
+    assert_eq!(config.load_theme().await.unwrap(), Theme::Solarized);
+
+    config.reset("theme").await.unwrap();
+    assert_eq!(config.load_theme().await.unwrap(), Theme::Default);
+
+    assert!(config.reset("no_such_setting").await.is_err());
+}
+
+#[tokio::test]
+async fn entries_list_every_setting_in_order() {
+    let fx = Fixture::new().await;
+    let config = &fx.services.config;
+
+    config.set("color.focus", "cyan").await.unwrap();
+
+    let entries = config.entries().await.unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.def.key).collect();
+
+    assert_eq!(
+        keys[..6],
+        [
+            "week_start",
+            "title_overflow",
+            "auto_rollover",
+            "stale_after",
+            "timezone",
+            "theme",
+        ]
+    );
+
+    let focus = entries.iter().find(|e| e.def.key == "color.focus").unwrap();
+    let text = entries.iter().find(|e| e.def.key == "color.text").unwrap();
+
+    assert!(focus.is_set);
+    assert_eq!(focus.display(), "cyan");
+    assert!(!text.is_set);
+    assert_eq!(text.display(), "(theme)");
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{Fixture, date, todo};
+
+#[tokio::test]
+async fn export_ics_writes_scheduled_todos_as_all_day_events() {
+    let fx = Fixture::new().await;
+
+    let rent = todo("Pay rent; call landlord, too")
+        .on(date(2025, 1, 17))
+        .notes("Bank \\ transfer\nref 42")
+        .tag("home")
+        .insert(&fx)
+        .await;
+    todo("Shipped").done().insert(&fx).await;
+    todo("Someday").backlog().insert(&fx).await;
+    todo(&"long ".repeat(30))
+        .on(date(2025, 1, 16))
+        .insert(&fx)
+        .await;
+
+    let calendar = fx.services.todos.export_ics().await.unwrap();
+
+    assert_eq!(calendar.events, 3);
+    assert!(
+        calendar
+            .text
+            .starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n")
+    );
+    assert!(calendar.text.ends_with("END:VCALENDAR\r\n"));
+    assert!(!calendar.text.contains("Someday"));
+
+    let lines: Vec<&str> = calendar.text.split("\r\n").collect();
+
+    assert!(lines.iter().all(|line| line.len() <= 75));
+
+    let summaries: Vec<&str> = lines
+        .iter()
+        .filter_map(|line| line.strip_prefix("SUMMARY:"))
+        .collect();
+
+    // Day order; folded lines continue with a leading space.
+    assert_eq!(summaries.len(), 3);
+    assert_eq!(summaries[0], "✓ Shipped");
+    assert!(summaries[1].starts_with("long long"));
+    assert_eq!(summaries[2], "Pay rent\\; call landlord\\, too");
+
+    for expected in [
+        format!("UID:{}@machich", rent.id),
+        "DTSTART;VALUE=DATE:20250117".to_string(),
+        "DTEND;VALUE=DATE:20250118".to_string(),
+        "DESCRIPTION:Bank \\\\ transfer\\nref 42".to_string(),
+        "CATEGORIES:home".to_string(),
+    ] {
+        assert!(lines.contains(&expected.as_str()), "missing {expected}");
+    }
+}
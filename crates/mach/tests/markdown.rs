@@ -0,0 +1,48 @@
+use machich::tui::markdown::{task_count, toggle_task};
+
+#[test]
+fn toggle_task_checks_and_unchecks() {
+    let notes = "- [ ] milk\n- [x] eggs\n";
+
+    let checked = toggle_task(notes, 0);
+    assert_eq!(checked, "- [x] milk\n- [x] eggs\n");
+
+    let unchecked = toggle_task(&checked, 1);
+    assert_eq!(unchecked, "- [x] milk\n- [ ] eggs\n");
+
+    assert_eq!(toggle_task(&toggle_task(notes, 0), 0), notes);
+}
+
+#[test]
+fn toggle_task_only_flips_the_box_after_the_marker() {
+    assert_eq!(
+        toggle_task("- [x] rename [ ] to [x]", 0),
+        "- [ ] rename [ ] to [x]"
+    );
+    assert_eq!(
+        toggle_task("* [ ] keep [x] as written", 0),
+        "* [x] keep [x] as written"
+    );
+    assert_eq!(toggle_task("  - [X] nested", 0), "  - [ ] nested");
+}
+
+#[test]
+fn toggle_task_handles_ordered_lists() {
+    let notes = "1. [ ] first\n10. [x] tenth";
+
+    assert_eq!(task_count(notes), 2);
+    assert_eq!(toggle_task(notes, 0), "1. [x] first\n10. [x] tenth");
+    assert_eq!(toggle_task(notes, 1), "1. [ ] first\n10. [ ] tenth");
+}
+
+#[test]
+fn toggle_task_skips_code_and_plain_brackets() {
+    let notes = "[ ] not a task\n```\n- [ ] in code\n```\n- [ ] real";
+
+    assert_eq!(task_count(notes), 1);
+    assert_eq!(
+        toggle_task(notes, 0),
+        "[ ] not a task\n```\n- [ ] in code\n```\n- [x] real"
+    );
+    assert_eq!(toggle_task(notes, 1), notes);
+}
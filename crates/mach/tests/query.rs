@@ -0,0 +1,127 @@
+mod common;
+
+use machich::service::{
+    config::WeekStart,
+    todo::{PlacementQuery, SortKey, StatusQuery, TodoQuery},
+};
+
+use common::date;
+
+/// Parse against Wednesday 2025-01-15 with Monday weeks.
+fn parse(input: &str) -> miette::Result<TodoQuery> {
+    TodoQuery::parse(input, date(2025, 1, 15), WeekStart::Monday)
+}
+
+#[test]
+fn empty_query_is_unfiltered() {
+    let query = parse("  ").unwrap();
+
+    assert!(query.is_unfiltered());
+    assert_eq!(query.sort, SortKey::Day);
+    assert_eq!(query.limit, None);
+}
+
+#[test]
+fn status_terms() {
+    assert_eq!(parse("status:done").unwrap().status, StatusQuery::Done);
+    assert_eq!(parse("is:pending").unwrap().status, StatusQuery::Pending);
+    assert_eq!(parse("status:any").unwrap().status, StatusQuery::Any);
+
+    let overdue = parse("is:overdue").unwrap();
+    assert_eq!(overdue.status, StatusQuery::Pending);
+    assert_eq!(overdue.from, None);
+    assert_eq!(overdue.to, Some(date(2025, 1, 14)));
+}
+
+#[test]
+fn due_comparisons() {
+    let on = parse("due:fri").unwrap();
+    assert_eq!(
+        (on.from, on.to),
+        (Some(date(2025, 1, 17)), Some(date(2025, 1, 17)))
+    );
+
+    let before = parse("due<2025-01-20").unwrap();
+    assert_eq!((before.from, before.to), (None, Some(date(2025, 1, 19))));
+
+    let until = parse("due<=today").unwrap();
+    assert_eq!(until.to, Some(date(2025, 1, 15)));
+
+    let after = parse("due>today").unwrap();
+    assert_eq!((after.from, after.to), (Some(date(2025, 1, 16)), None));
+
+    let since = parse("due>=-7d").unwrap();
+    assert_eq!(since.from, Some(date(2025, 1, 8)));
+}
+
+#[test]
+fn placement_terms() {
+    assert_eq!(
+        parse("in:backlog").unwrap().placement,
+        PlacementQuery::Backlog(None)
+    );
+    assert_eq!(
+        parse("col:2").unwrap().placement,
+        PlacementQuery::Backlog(Some(1))
+    );
+
+    let week = parse("in:week").unwrap();
+    assert_eq!(
+        (week.from, week.to),
+        (Some(date(2025, 1, 13)), Some(date(2025, 1, 19)))
+    );
+}
+
+#[test]
+fn tags_titles_and_words() {
+    let query = parse("#Work tag:home title:/^call/ the bank").unwrap();
+
+    assert_eq!(query.tags, ["work", "home"]);
+    assert_eq!(query.title.unwrap().as_str(), "^call");
+    assert_eq!(query.text.as_deref(), Some("the bank"));
+
+    // A bare title word is matched literally.
+    let literal = parse("title:a.b").unwrap();
+    assert!(literal.title.as_ref().unwrap().is_match("A.B"));
+    assert!(!literal.title.unwrap().is_match("axb"));
+}
+
+#[test]
+fn sort_and_limit_do_not_filter() {
+    let query = parse("sort:title limit:5").unwrap();
+
+    assert_eq!(query.sort, SortKey::Title);
+    assert_eq!(query.limit, Some(5));
+    assert!(query.is_unfiltered());
+}
+
+#[test]
+fn terms_override_defaults() {
+    let today = date(2025, 1, 15);
+    let base = || TodoQuery::new().status(StatusQuery::Pending);
+
+    let kept = base()
+        .with_terms("#work", today, WeekStart::Monday)
+        .unwrap();
+    assert_eq!(kept.status, StatusQuery::Pending);
+
+    let any = base()
+        .with_terms("status:any", today, WeekStart::Monday)
+        .unwrap();
+    assert_eq!(any.status, StatusQuery::Any);
+}
+
+#[test]
+fn invalid_terms_are_errors() {
+    for input in [
+        "due:someday-ish",
+        "col:two",
+        "col:0",
+        "limit:-1",
+        "sort:size",
+        "title:/(/",
+        "color:red",
+    ] {
+        assert!(parse(input).is_err(), "{input} should not parse");
+    }
+}
@@ -0,0 +1,49 @@
+use machich::tui::text::{truncate, wrap};
+
+#[test]
+fn wrap_breaks_on_whitespace() {
+    assert_eq!(wrap("Buy milk and eggs", 8), ["Buy milk", "and eggs"]);
+    assert_eq!(wrap("  spaced   out  ", 20), ["spaced out"]);
+}
+
+#[test]
+fn wrap_hard_breaks_long_words() {
+    assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+    assert_eq!(
+        wrap("to supercalifragilistic", 8),
+        ["to", "supercal", "ifragili", "stic"]
+    );
+}
+
+#[test]
+fn wrap_measures_display_width() {
+    // Each of these characters takes two columns.
+    assert_eq!(wrap("日本語 テスト", 6), ["日本語", "テスト"]);
+    assert_eq!(wrap("日本語", 4), ["日本", "語"]);
+}
+
+#[test]
+fn wrap_edge_cases() {
+    assert_eq!(wrap("", 10), [""]);
+    assert_eq!(wrap("anything", 0), [""]);
+}
+
+#[test]
+fn truncate_keeps_text_that_fits() {
+    assert_eq!(truncate("Buy milk", 8), ("Buy milk".to_string(), false));
+}
+
+#[test]
+fn truncate_ends_in_an_ellipsis() {
+    assert_eq!(truncate("Buy milk", 7), ("Buy mi…".to_string(), true));
+    assert_eq!(truncate("Buy milk", 1), ("…".to_string(), true));
+    assert_eq!(truncate("Buy milk", 0), (String::new(), true));
+}
+
+#[test]
+fn truncate_never_splits_a_wide_character() {
+    let (text, cut) = truncate("日本語", 4);
+
+    assert_eq!(text, "日…");
+    assert!(cut);
+}
@@ -0,0 +1,146 @@
+use machich::tui::harness::parse_keys;
+use machich::tui::text_input::TextInput;
+
+/// Feed a key script to `input`, returning whether every key was handled.
+fn type_keys(input: &mut TextInput, script: &str) -> bool {
+    parse_keys(script)
+        .unwrap()
+        .into_iter()
+        .all(|key| input.handle_key(key))
+}
+
+/// The value with the cursor shown as `|`.
+fn show(input: &TextInput) -> String {
+    let (line, col) = input.cursor_position();
+    let mut lines: Vec<String> = input.value().split('\n').map(String::from).collect();
+
+    let at = lines[line]
+        .char_indices()
+        .nth(col)
+        .map_or(lines[line].len(), |(idx, _)| idx);
+
+    lines[line].insert(at, '|');
+
+    lines.join("\n")
+}
+
+#[test]
+fn typing_inserts_at_the_cursor() {
+    let mut input = TextInput::new("milk");
+
+    assert!(type_keys(&mut input, "<Home>Buy <End>!"));
+    assert_eq!(show(&input), "Buy milk!|");
+}
+
+#[test]
+fn cursor_moves_by_char_and_word() {
+    let mut input = TextInput::new("one two three");
+
+    type_keys(&mut input, "<C-Left>");
+    assert_eq!(show(&input), "one two |three");
+
+    type_keys(&mut input, "<A-b><Left>");
+    assert_eq!(show(&input), "one| two three");
+
+    type_keys(&mut input, "<A-f><C-f>");
+    assert_eq!(show(&input), "one two |three");
+
+    type_keys(&mut input, "<C-a>");
+    assert_eq!(show(&input), "|one two three");
+
+    type_keys(&mut input, "<C-e>");
+    assert_eq!(show(&input), "one two three|");
+}
+
+#[test]
+fn deleting_words_and_lines() {
+    let mut input = TextInput::new("one two three");
+
+    type_keys(&mut input, "<C-w>");
+    assert_eq!(show(&input), "one two |");
+
+    type_keys(&mut input, "<A-BS>");
+    assert_eq!(show(&input), "one |");
+
+    type_keys(&mut input, "<BS><Home><Del>");
+    assert_eq!(show(&input), "|ne");
+
+    type_keys(&mut input, "<Right><C-k>");
+    assert_eq!(show(&input), "n|");
+
+    type_keys(&mut input, "<C-u>");
+    assert_eq!(show(&input), "|");
+    assert!(input.is_empty());
+
+    // Deleting at either end is a no-op.
+    type_keys(&mut input, "<BS><Del><C-d>");
+    assert_eq!(show(&input), "|");
+}
+
+#[test]
+fn multibyte_text_edits_whole_characters() {
+    let mut input = TextInput::new("café ☕");
+
+    type_keys(&mut input, "<BS><BS><BS>");
+    assert_eq!(show(&input), "caf|");
+
+    type_keys(&mut input, "<Left>é<Right>!");
+    assert_eq!(show(&input), "caéf!|");
+}
+
+#[test]
+fn unhandled_keys_are_left_to_the_caller() {
+    let mut input = TextInput::new("x");
+
+    for script in ["<Enter>", "<Esc>", "<Tab>", "<Up>", "<C-x>"] {
+        assert!(!type_keys(&mut input, script), "{script}");
+    }
+
+    assert_eq!(show(&input), "x|");
+}
+
+#[test]
+fn single_line_inputs_flatten_newlines() {
+    let mut input = TextInput::new("");
+
+    input.insert_str("one\r\ntwo\nthree");
+    assert_eq!(input.value(), "one two three");
+    assert!(!type_keys(&mut input, "<C-j>"));
+}
+
+#[test]
+fn multiline_inputs_move_between_lines() {
+    let mut input = TextInput::multiline("first line\nsecond");
+
+    type_keys(&mut input, "<Up>");
+    assert_eq!(show(&input), "first |line\nsecond");
+
+    // Moving to a shorter line clamps the column.
+    type_keys(&mut input, "<End><Down>");
+    assert_eq!(show(&input), "first line\nsecond|");
+
+    type_keys(&mut input, "<C-j>third");
+    assert_eq!(input.line_count(), 3);
+    assert_eq!(show(&input), "first line\nsecond\nthird|");
+
+    // Line-wise keys stay on the current line.
+    type_keys(&mut input, "<Up><C-u>");
+    assert_eq!(show(&input), "first line\n|d\nthird");
+}
+
+#[test]
+fn set_value_moves_the_cursor_to_the_end() {
+    let mut input = TextInput::new("old");
+
+    type_keys(&mut input, "<Home>");
+    input.set_value("new value");
+
+    assert_eq!(show(&input), "new value|");
+    assert_eq!(input.into_value(), "new value");
+}
+
+#[test]
+fn key_scripts_reject_unknown_key_names() {
+    assert_eq!(parse_keys("a<lt><C-w>").unwrap().len(), 3);
+    assert!(parse_keys("<Nope>").is_err());
+}
@@ -0,0 +1,615 @@
+mod common;
+
+use chrono::Duration;
+use machich::{
+    entity::todo as todo_entity,
+    service::{
+        Services,
+        clock::Clock,
+        todo::{ListOptions, ListScope, MovePlacement, ReorderDirection},
+    },
+};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait, IntoActiveModel};
+
+use common::{Fixture, date, todo};
+
+#[tokio::test]
+async fn add_schedules_for_the_given_day() {
+    let fx = Fixture::new().await;
+
+    let model = fx
+        .services
+        .todos
+        .add("Write report", Some(fx.today()), Some("draft first".into()))
+        .await
+        .unwrap();
+
+    assert_eq!(model.title, "Write report");
+    assert_eq!(model.status, "pending");
+    assert_eq!(model.scheduled_for, Some(fx.today()));
+    assert_eq!(model.notes.as_deref(), Some("draft first"));
+    assert_eq!(model.backlog_column, 0);
+    assert_eq!(model.created_at, fx.clock.now());
+}
+
+#[tokio::test]
+async fn add_without_date_goes_to_backlog() {
+    let fx = Fixture::new().await;
+
+    todo("Someday").backlog().insert(&fx).await;
+
+    assert_eq!(fx.titles(ListScope::Backlog).await, ["Someday"]);
+    assert!(fx.titles_on(fx.today()).await.is_empty());
+}
+
+#[tokio::test]
+async fn add_puts_new_todos_on_top() {
+    let fx = Fixture::new().await;
+
+    todo("first").insert(&fx).await;
+    todo("second").insert(&fx).await;
+    todo("third").insert(&fx).await;
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["third", "second", "first"]);
+}
+
+#[tokio::test]
+async fn add_many_keeps_the_list_order_at_the_top_or_bottom() {
+    let fx = Fixture::new().await;
+    let todos = &fx.services.todos;
+    let titles = |names: &[&str]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    todo("existing").insert(&fx).await;
+
+    todos
+        .add_many(
+            &titles(&["a", "b"]),
+            Some(fx.today()),
+            None,
+            None,
+            MovePlacement::Top,
+        )
+        .await
+        .unwrap();
+
+    let added = todos
+        .add_many(
+            &titles(&["y", "z"]),
+            Some(fx.today()),
+            None,
+            Some("shared".into()),
+            MovePlacement::Bottom,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        fx.titles_on(fx.today()).await,
+        ["a", "b", "existing", "y", "z"]
+    );
+    assert_eq!(added[0].title, "y");
+    assert!(
+        added
+            .iter()
+            .all(|todo| todo.notes.as_deref() == Some("shared"))
+    );
+}
+
+#[tokio::test]
+async fn add_many_files_backlog_todos_under_a_column() {
+    let fx = Fixture::new().await;
+    let todos = &fx.services.todos;
+
+    todo("waiting").column(2).insert(&fx).await;
+
+    let added = todos
+        .add_many(&["new".into()], None, Some(2), None, MovePlacement::Bottom)
+        .await
+        .unwrap();
+
+    assert_eq!(added[0].backlog_column, 2);
+    assert_eq!(added[0].scheduled_for, None);
+
+    let column: Vec<String> = todos
+        .list(ListOptions {
+            scope: ListScope::Backlog,
+            include_done: false,
+        })
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|todo| todo.backlog_column == 2)
+        .map(|todo| todo.title)
+        .collect();
+
+    assert_eq!(column, ["waiting", "new"]);
+
+    // The column is ignored for scheduled todos.
+    let added = todos
+        .add_many(
+            &["dated".into()],
+            Some(fx.today()),
+            Some(2),
+            None,
+            MovePlacement::Top,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(added[0].backlog_column, 0);
+}
+
+#[tokio::test]
+async fn add_above_done_todos_keeps_them_last() {
+    let fx = Fixture::new().await;
+
+    todo("done").done().insert(&fx).await;
+    todo("pending").insert(&fx).await;
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["pending", "done"]);
+}
+
+#[tokio::test]
+async fn list_sorts_done_after_pending() {
+    let fx = Fixture::new().await;
+
+    let a = todo("a").insert(&fx).await;
+    todo("b").insert(&fx).await;
+    let c = todo("c").insert(&fx).await;
+
+    // Completing the top todo sinks it below everything still pending.
+    fx.services.todos.mark_done(c.id, fx.today()).await.unwrap();
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["b", "a", "c"]);
+
+    fx.services.todos.mark_done(a.id, fx.today()).await.unwrap();
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["b", "c", "a"]);
+}
+
+#[tokio::test]
+async fn list_hides_done_unless_asked() {
+    let fx = Fixture::new().await;
+
+    todo("open").insert(&fx).await;
+    todo("closed").done().insert(&fx).await;
+
+    let visible: Vec<String> = fx
+        .services
+        .todos
+        .list(ListOptions::today(fx.today()))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.title)
+        .collect();
+
+    assert_eq!(visible, ["open"]);
+    assert_eq!(fx.titles_on(fx.today()).await, ["open", "closed"]);
+}
+
+#[tokio::test]
+async fn list_is_scoped_to_one_day() {
+    let fx = Fixture::new().await;
+    let tomorrow = fx.today() + Duration::days(1);
+
+    todo("today").insert(&fx).await;
+    todo("tomorrow").on(tomorrow).insert(&fx).await;
+    todo("backlog").backlog().insert(&fx).await;
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["today"]);
+    assert_eq!(fx.titles_on(tomorrow).await, ["tomorrow"]);
+    assert_eq!(fx.titles(ListScope::Backlog).await, ["backlog"]);
+}
+
+#[tokio::test]
+async fn mark_done_on_backlog_item_moves_it_to_today() {
+    let fx = Fixture::new().await;
+
+    todo("existing done").done().insert(&fx).await;
+    todo("pending").insert(&fx).await;
+
+    let someday = todo("someday").column(2).insert(&fx).await;
+
+    let done = fx
+        .services
+        .todos
+        .mark_done(someday.id, fx.today())
+        .await
+        .unwrap();
+
+    assert_eq!(done.status, "done");
+    assert_eq!(done.scheduled_for, Some(fx.today()));
+    assert!(fx.titles(ListScope::Backlog).await.is_empty());
+    assert_eq!(
+        fx.titles_on(fx.today()).await,
+        ["pending", "existing done", "someday"]
+    );
+}
+
+#[tokio::test]
+async fn mark_done_keeps_scheduled_date() {
+    let fx = Fixture::new().await;
+    let friday = date(2025, 1, 17);
+
+    let model = todo("friday").on(friday).insert(&fx).await;
+
+    let done = fx
+        .services
+        .todos
+        .mark_done(model.id, fx.today())
+        .await
+        .unwrap();
+
+    assert_eq!(done.scheduled_for, Some(friday));
+}
+
+#[tokio::test]
+async fn mark_pending_returns_todo_to_the_top() {
+    let fx = Fixture::new().await;
+
+    let model = todo("was done").done().insert(&fx).await;
+    todo("a").insert(&fx).await;
+    todo("b").insert(&fx).await;
+
+    fx.services.todos.mark_pending(model.id).await.unwrap();
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["was done", "b", "a"]);
+}
+
+#[tokio::test]
+async fn move_to_scope_top_and_bottom() {
+    let fx = Fixture::new().await;
+    let tomorrow = fx.today() + Duration::days(1);
+
+    todo("x").on(tomorrow).insert(&fx).await;
+    todo("y").on(tomorrow).insert(&fx).await;
+    todo("done").on(tomorrow).done().insert(&fx).await;
+
+    let top = todo("to top").insert(&fx).await;
+    let bottom = todo("to bottom").insert(&fx).await;
+
+    let todos = &fx.services.todos;
+
+    todos
+        .move_to_scope(top.id, ListScope::Day(tomorrow), MovePlacement::Top)
+        .await
+        .unwrap();
+
+    todos
+        .move_to_scope(bottom.id, ListScope::Day(tomorrow), MovePlacement::Bottom)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        fx.titles_on(tomorrow).await,
+        ["to top", "y", "x", "to bottom", "done"]
+    );
+    assert!(fx.titles_on(fx.today()).await.is_empty());
+}
+
+#[tokio::test]
+async fn move_to_backlog_clears_the_date() {
+    let fx = Fixture::new().await;
+
+    todo("already").backlog().insert(&fx).await;
+    let model = todo("later").insert(&fx).await;
+
+    let moved = fx
+        .services
+        .todos
+        .move_to_scope(model.id, ListScope::Backlog, MovePlacement::Bottom)
+        .await
+        .unwrap();
+
+    assert_eq!(moved.scheduled_for, None);
+    assert_eq!(fx.titles(ListScope::Backlog).await, ["already", "later"]);
+}
+
+#[tokio::test]
+async fn move_done_todo_to_bottom_stays_with_done_group() {
+    let fx = Fixture::new().await;
+    let tomorrow = fx.today() + Duration::days(1);
+
+    todo("pending").on(tomorrow).insert(&fx).await;
+    todo("done there").on(tomorrow).done().insert(&fx).await;
+
+    let done_here = todo("done here").done().insert(&fx).await;
+
+    fx.services
+        .todos
+        .move_to_scope(
+            done_here.id,
+            ListScope::Day(tomorrow),
+            MovePlacement::Bottom,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        fx.titles_on(tomorrow).await,
+        ["pending", "done there", "done here"]
+    );
+}
+
+#[tokio::test]
+async fn reorder_swaps_neighbours() {
+    let fx = Fixture::new().await;
+
+    todo("c").insert(&fx).await;
+    let b = todo("b").insert(&fx).await;
+    todo("a").insert(&fx).await;
+
+    let todos = &fx.services.todos;
+
+    todos.reorder(b.id, ReorderDirection::Up).await.unwrap();
+    assert_eq!(fx.titles_on(fx.today()).await, ["b", "a", "c"]);
+
+    todos.reorder(b.id, ReorderDirection::Down).await.unwrap();
+    todos.reorder(b.id, ReorderDirection::Down).await.unwrap();
+    assert_eq!(fx.titles_on(fx.today()).await, ["a", "c", "b"]);
+}
+
+#[tokio::test]
+async fn reorder_stops_at_the_edges() {
+    let fx = Fixture::new().await;
+
+    let bottom = todo("bottom").insert(&fx).await;
+    let top = todo("top").insert(&fx).await;
+
+    let todos = &fx.services.todos;
+
+    todos.reorder(top.id, ReorderDirection::Up).await.unwrap();
+    todos
+        .reorder(bottom.id, ReorderDirection::Down)
+        .await
+        .unwrap();
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["top", "bottom"]);
+}
+
+#[tokio::test]
+async fn reorder_does_not_cross_into_done_group() {
+    let fx = Fixture::new().await;
+
+    todo("done").done().insert(&fx).await;
+    let pending = todo("pending").insert(&fx).await;
+
+    fx.services
+        .todos
+        .reorder(pending.id, ReorderDirection::Down)
+        .await
+        .unwrap();
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["pending", "done"]);
+}
+
+#[tokio::test]
+async fn reorder_only_rewrites_the_moved_todo() {
+    let fx = Fixture::new().await;
+
+    let c = todo("c").insert(&fx).await;
+    let b = todo("b").insert(&fx).await;
+    let a = todo("a").insert(&fx).await;
+
+    fx.services
+        .todos
+        .reorder(c.id, ReorderDirection::Up)
+        .await
+        .unwrap();
+
+    let todos = &fx.services.todos;
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["a", "c", "b"]);
+    assert_eq!(todos.get(a.id).await.unwrap(), a);
+    assert_eq!(todos.get(b.id).await.unwrap(), b);
+}
+
+#[tokio::test]
+async fn order_indexes_stay_positive() {
+    let fx = Fixture::new().await;
+
+    for n in 0..50 {
+        todo(&format!("todo {n}")).insert(&fx).await;
+    }
+
+    let models = fx
+        .services
+        .todos
+        .list(ListOptions::today(fx.today()))
+        .await
+        .unwrap();
+
+    assert_eq!(models[0].title, "todo 49");
+    assert!(models.iter().all(|model| model.order_index > 0));
+}
+
+#[tokio::test]
+async fn move_to_position_within_and_across_columns() {
+    let fx = Fixture::new().await;
+    let tomorrow = fx.today() + Duration::days(1);
+
+    todo("c").insert(&fx).await;
+    todo("b").insert(&fx).await;
+    let a = todo("a").insert(&fx).await;
+    todo("y").on(tomorrow).insert(&fx).await;
+    todo("x").on(tomorrow).insert(&fx).await;
+    todo("done").on(tomorrow).done().insert(&fx).await;
+
+    let todos = &fx.services.todos;
+
+    todos
+        .move_to_position(a.id, ListScope::Day(fx.today()), 1)
+        .await
+        .unwrap();
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["b", "a", "c"]);
+
+    todos
+        .move_to_position(a.id, ListScope::Day(tomorrow), 1)
+        .await
+        .unwrap();
+
+    assert_eq!(fx.titles_on(tomorrow).await, ["x", "a", "y", "done"]);
+
+    // Positions past the end clamp to the bottom of the group.
+    todos
+        .move_to_position(a.id, ListScope::Day(tomorrow), 99)
+        .await
+        .unwrap();
+
+    assert_eq!(fx.titles_on(tomorrow).await, ["x", "y", "a", "done"]);
+}
+
+#[tokio::test]
+async fn crowded_columns_are_respaced() {
+    let fx = Fixture::new().await;
+    let db = fx.services.todos.connection();
+
+    let c = todo("c").insert(&fx).await;
+    let b = todo("b").insert(&fx).await;
+    let a = todo("a").insert(&fx).await;
+
+    // Dense, partly negative indexes as older versions wrote them.
+    for (model, index) in [(a, -1), (b, 0), (c.clone(), 1)] {
+        let mut active = model.into_active_model();
+
+        active.order_index = Set(index);
+        active.update(db).await.unwrap();
+    }
+
+    fx.services
+        .todos
+        .reorder(c.id, ReorderDirection::Up)
+        .await
+        .unwrap();
+
+    todo("top").insert(&fx).await;
+
+    assert_eq!(fx.titles_on(fx.today()).await, ["top", "a", "c", "b"]);
+
+    let models = todo_entity::Entity::find().all(db).await.unwrap();
+
+    assert!(models.iter().all(|model| model.order_index > 0));
+}
+
+#[tokio::test]
+async fn rollover_moves_overdue_todos_in_order() {
+    let mut fx = Fixture::new().await;
+    let monday = date(2025, 1, 13);
+    let tuesday = date(2025, 1, 14);
+
+    todo("mon 2").on(monday).insert(&fx).await;
+    todo("mon 1").on(monday).insert(&fx).await;
+    todo("tue 1").on(tuesday).insert(&fx).await;
+    todo("today").insert(&fx).await;
+
+    let moved = fx.services.todos.rollover_to(fx.today()).await.unwrap();
+
+    assert_eq!(moved.len(), 3);
+    assert!(fx.titles_on(monday).await.is_empty());
+    assert!(fx.titles_on(tuesday).await.is_empty());
+
+    // Rolled todos land below today's, oldest day first, keeping their
+    // order within each day.
+    let titles = fx.titles_on(fx.today()).await;
+
+    assert_eq!(titles, ["today", "mon 1", "mon 2", "tue 1"]);
+
+    fx.advance_days(1).await;
+
+    assert_eq!(fx.services.rolled_over().await.unwrap().len(), 4);
+    assert_eq!(fx.titles_on(fx.today()).await, titles);
+}
+
+#[tokio::test]
+async fn rollover_skips_done_backlog_and_future_todos() {
+    let fx = Fixture::new().await;
+    let yesterday = fx.today() - Duration::days(1);
+    let tomorrow = fx.today() + Duration::days(1);
+
+    todo("done yesterday")
+        .on(yesterday)
+        .done()
+        .insert(&fx)
+        .await;
+    todo("backlog").backlog().insert(&fx).await;
+    todo("tomorrow").on(tomorrow).insert(&fx).await;
+
+    let moved = fx.services.todos.rollover_to(fx.today()).await.unwrap();
+
+    assert!(moved.is_empty());
+    assert_eq!(fx.titles_on(yesterday).await, ["done yesterday"]);
+    assert_eq!(fx.titles_on(tomorrow).await, ["tomorrow"]);
+}
+
+#[tokio::test]
+async fn rollover_records_count_and_original_day() {
+    let mut fx = Fixture::new().await;
+    let yesterday = fx.today() - Duration::days(1);
+
+    let model = todo("slipping").on(yesterday).insert(&fx).await;
+
+    fx.services.todos.rollover_to(fx.today()).await.unwrap();
+    fx.advance_days(2).await;
+
+    let model = fx.services.todos.get(model.id).await.unwrap();
+
+    assert_eq!(model.scheduled_for, Some(fx.today()));
+    assert_eq!(model.rollover_count(), 2);
+    assert_eq!(model.rolled_from(), Some(yesterday));
+}
+
+#[tokio::test]
+async fn rolled_over_todos_are_remembered_across_restarts() {
+    let fx = Fixture::new().await;
+    let yesterday = fx.today() - Duration::days(1);
+
+    let slipped = todo("slipped").on(yesterday).insert(&fx).await;
+    let done = todo("rolled then done").on(yesterday).insert(&fx).await;
+    fx.services.todos.rollover_to(fx.today()).await.unwrap();
+    todo("new today").insert(&fx).await;
+
+    fx.services
+        .todos
+        .mark_done(done.id, fx.today())
+        .await
+        .unwrap();
+
+    let model = fx.services.todos.get(slipped.id).await.unwrap();
+    assert_eq!(model.rolled_on(), Some(fx.today()));
+
+    // A second start moves nothing but still finds what rolled over today.
+    let conn = fx.services.todos.connection().clone();
+    let services = Services::open(conn, fx.clock.clone()).await.unwrap();
+
+    let titles: Vec<String> = services
+        .rolled_over()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|model| model.title)
+        .collect();
+
+    assert_eq!(titles, ["slipped"]);
+}
+
+#[tokio::test]
+async fn startup_rollover_respects_auto_rollover() {
+    let fx = Fixture::new().await;
+    let yesterday = fx.today() - Duration::days(1);
+
+    fx.services
+        .config
+        .set("auto_rollover", "false")
+        .await
+        .unwrap();
+
+    todo("stays").on(yesterday).insert(&fx).await;
+
+    let conn = fx.services.todos.connection().clone();
+    let services = Services::open(conn, fx.clock.clone()).await.unwrap();
+
+    assert!(services.rolled_over().await.unwrap().is_empty());
+    assert_eq!(fx.titles_on(yesterday).await, ["stays"]);
+}
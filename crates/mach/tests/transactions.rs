@@ -0,0 +1,181 @@
+mod common;
+
+use std::sync::Arc;
+
+use machich::service::{
+    Services,
+    clock::{FixedClock, TimeZonePref},
+    connection::init_database,
+    todo::{ListOptions, ListScope, ReorderDirection, Slot, TodoService},
+};
+use miette::miette;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use common::{Fixture, date, todo};
+
+#[tokio::test]
+async fn transaction_commits_every_change_together() {
+    let fx = Fixture::new().await;
+    let today = fx.today();
+
+    let ids = fx
+        .services
+        .todos
+        .transaction(async |txn| {
+            let a = txn.add("a", Some(today), 0, None, Slot::Top).await?;
+            let b = txn.add("b", Some(today), 0, None, Slot::Top).await?;
+
+            txn.mark_done(a.id, today).await?;
+
+            Ok([a.id, b.id])
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(ids.len(), 2);
+    assert_eq!(fx.titles_on(today).await, ["b", "a"]);
+}
+
+#[tokio::test]
+async fn failed_transaction_rolls_back() {
+    let fx = Fixture::new().await;
+    let today = fx.today();
+
+    let existing = todo("existing").insert(&fx).await;
+
+    let result: miette::Result<()> = fx
+        .services
+        .todos
+        .transaction(async |txn| {
+            txn.add("new", Some(today), 0, None, Slot::Top).await?;
+            txn.delete(existing.id).await?;
+
+            Err(miette!("changed my mind"))
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(fx.titles_on(today).await, ["existing"]);
+}
+
+#[tokio::test]
+async fn transaction_sees_its_own_writes() {
+    let fx = Fixture::new().await;
+    let today = fx.today();
+
+    let title = fx
+        .services
+        .todos
+        .transaction(async |txn| {
+            let model = txn.add("draft", Some(today), 0, None, Slot::Top).await?;
+
+            txn.update_title(model.id, "final".into()).await?;
+
+            Ok(txn.get(model.id).await?.title)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(title, "final");
+}
+
+#[tokio::test]
+async fn failing_bulk_operation_changes_nothing() {
+    let fx = Fixture::new().await;
+
+    let a = todo("a").insert(&fx).await;
+
+    let result = fx
+        .services
+        .todos
+        .complete_many(&[a.id, Uuid::new_v4()], fx.today())
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(fx.services.todos.get(a.id).await.unwrap().status, "pending");
+}
+
+/// Two `mach` processes writing to the same database file at once.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_writers_keep_order_consistent() {
+    let path = std::env::temp_dir().join(format!("mach-conflict-{}.db", Uuid::new_v4()));
+    let today = date(2025, 1, 15);
+
+    let mut processes = Vec::new();
+
+    for _ in 0..2 {
+        let conn = init_database(&path).await.unwrap();
+        let clock = Arc::new(FixedClock::on(today, TimeZonePref::Local));
+
+        processes.push(Services::open(conn, clock).await.unwrap().todos);
+    }
+
+    let seeded = processes[0].add("seed", Some(today), None).await.unwrap();
+
+    let mut tasks = JoinSet::new();
+
+    for (n, todos) in processes.iter().cycle().take(8).enumerate() {
+        let todos: TodoService = todos.clone();
+
+        tasks.spawn(async move {
+            for round in 0..5 {
+                todos
+                    .add(format!("todo {n}.{round}"), Some(today), None)
+                    .await?;
+
+                let direction = if round % 2 == 0 {
+                    ReorderDirection::Down
+                } else {
+                    ReorderDirection::Up
+                };
+
+                todos.reorder(seeded.id, direction).await?;
+            }
+
+            todos.rollover_to(today).await
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        result.unwrap().unwrap();
+    }
+
+    let models = processes[0]
+        .list(ListOptions {
+            scope: ListScope::Day(today),
+            include_done: true,
+        })
+        .await
+        .unwrap();
+
+    let mut indexes: Vec<i64> = models.iter().map(|model| model.order_index).collect();
+
+    indexes.dedup();
+
+    assert_eq!(models.len(), 41);
+    assert_eq!(indexes.len(), models.len(), "order indexes must be unique");
+
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+}
+
+#[tokio::test]
+async fn retag_many_applies_every_token_or_none() {
+    let fx = Fixture::new().await;
+    let a = todo("a").tag("work").insert(&fx).await;
+    let b = todo("b").insert(&fx).await;
+    let todos = &fx.services.todos;
+
+    todos
+        .retag_many(&[a.id, b.id], &["+home", "-work", "Errands"])
+        .await
+        .unwrap();
+
+    assert_eq!(todos.get(a.id).await.unwrap().tags(), ["home", "errands"]);
+    assert_eq!(todos.get(b.id).await.unwrap().tags(), ["home", "errands"]);
+
+    assert!(todos.retag_many(&[a.id], &["+later", "-"]).await.is_err());
+    assert_eq!(todos.get(a.id).await.unwrap().tags(), ["home", "errands"]);
+}
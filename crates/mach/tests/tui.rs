@@ -0,0 +1,721 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::Duration;
+use insta::{assert_debug_snapshot, assert_snapshot};
+use machich::service::config::Theme;
+use machich::service::todo::{ListScope, PlacementQuery, Slot, TodoQuery};
+use machich::tui::harness::Harness;
+use machich::tui::palette::Palette;
+use ratatui::style::Color;
+use tokio::runtime::Runtime;
+use unicode_width::UnicodeWidthStr;
+
+use common::{Fixture, date, todo};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 20;
+
+/// A harness over a seeded fixture, with the runtime its app blocks on.
+struct Tui {
+    harness: Harness,
+    fx_clock: std::sync::Arc<machich::service::clock::FixedClock>,
+    runtime: Runtime,
+}
+
+impl Tui {
+    fn titles(&self, scope: ListScope) -> Vec<String> {
+        let services = self.harness.services();
+
+        self.runtime.block_on(async {
+            services
+                .todos
+                .list(machich::service::todo::ListOptions {
+                    scope,
+                    include_done: true,
+                })
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|todo| todo.title)
+                .collect()
+        })
+    }
+}
+
+impl std::ops::Deref for Tui {
+    type Target = Harness;
+
+    fn deref(&self) -> &Harness {
+        &self.harness
+    }
+}
+
+impl std::ops::DerefMut for Tui {
+    fn deref_mut(&mut self) -> &mut Harness {
+        &mut self.harness
+    }
+}
+
+fn tui(seed: impl AsyncFnOnce(&Fixture)) -> Tui {
+    let runtime = Runtime::new().unwrap();
+
+    let fx = runtime.block_on(async {
+        let mut fx = Fixture::new().await;
+
+        seed(&fx).await;
+
+        // Pick up settings the seed changed.
+        fx.services.reload_preferences().await.unwrap();
+
+        fx
+    });
+
+    let harness = Harness::new(fx.services, runtime.handle().clone(), WIDTH, HEIGHT);
+
+    Tui {
+        harness,
+        fx_clock: fx.clock,
+        runtime,
+    }
+}
+
+/// A small week: a couple of todos today, one done, one later in the week.
+async fn seed_week(fx: &Fixture) {
+    todo("Pay rent").done().insert(fx).await;
+    todo("Review PR").tag("work").insert(fx).await;
+    todo("Write report")
+        .notes("Cover **Q4** numbers\n\n- revenue\n- churn")
+        .insert(fx)
+        .await;
+    todo("Dentist").on(date(2025, 1, 17)).insert(fx).await;
+    todo("Plan trip").backlog().insert(fx).await;
+    todo("Read book").column(1).insert(fx).await;
+}
+
+#[test]
+fn board_view() {
+    let mut tui = tui(seed_week);
+
+    assert_debug_snapshot!(tui.buffer());
+}
+
+#[test]
+fn text_keeps_wide_characters_in_their_cells() {
+    let mut tui = tui(async |fx| {
+        todo("日本語 memo").insert(fx).await;
+    });
+
+    let text = tui.text();
+    let rows: Vec<&str> = text.lines().skip(2).take(2).collect();
+
+    assert!(rows[0].contains("│日本語 memo   │"), "{text}");
+    // Borders line up with the empty row below.
+    assert_eq!(rows[0].width(), rows[1].width(), "{text}");
+}
+
+#[test]
+fn board_cursor_moves_between_rows_and_days() {
+    let mut tui = tui(seed_week);
+
+    tui.press("j");
+    assert_debug_snapshot!("board_cursor_second_row", tui.buffer());
+
+    tui.press("ll");
+    assert_debug_snapshot!("board_cursor_friday", tui.buffer());
+}
+
+#[test]
+fn backlog_view() {
+    let mut tui = tui(seed_week);
+
+    tui.press("b");
+
+    assert_debug_snapshot!(tui.buffer());
+}
+
+#[test]
+fn detail_view() {
+    let mut tui = tui(seed_week);
+
+    tui.press("<Space>");
+
+    assert_debug_snapshot!(tui.buffer());
+}
+
+#[test]
+fn settings_view() {
+    let mut tui = tui(seed_week);
+
+    tui.press("gs");
+
+    assert_debug_snapshot!(tui.buffer());
+}
+
+#[test]
+fn settings_modal_changes_and_resets_values() {
+    let mut tui = tui(seed_week);
+    let config = tui.services().config.clone();
+
+    // Cycle week_start forward, then type a new stale_after.
+    tui.press("gsl");
+
+    assert!(tui.text().contains("week_start      monday"));
+    assert!(status_bar(&mut tui).ends_with("Jan 13 – Jan 19, 2025"));
+
+    tui.press("jjj<Enter><C-u>5<Enter>");
+
+    assert_eq!(tui.runtime.block_on(config.load_stale_after()).unwrap(), 5);
+
+    // Invalid input is reported and leaves the value alone.
+    tui.press("<Enter><C-u>lots<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("stale_after must be a number"));
+    assert_eq!(tui.runtime.block_on(config.load_stale_after()).unwrap(), 5);
+
+    tui.press("kkkr");
+
+    assert!(tui.text().contains("week_start      sunday"));
+    assert!(status_bar(&mut tui).starts_with("week_start reset"));
+}
+
+#[test]
+fn add_todo_view() {
+    let mut tui = tui(seed_week);
+
+    tui.press("aBuy milk");
+
+    assert_debug_snapshot!(tui.buffer());
+
+    tui.press("<Enter>");
+
+    assert_eq!(tui.titles(ListScope::Day(date(2025, 1, 15)))[0], "Buy milk");
+}
+
+#[test]
+fn backlog_columns_can_be_added_renamed_moved_and_removed() {
+    let mut tui = tui(seed_week);
+    let backlog = tui.services().backlog.clone();
+    let columns = |tui: &Tui| tui.runtime.block_on(backlog.columns()).unwrap();
+
+    tui.press("bAReading<Enter>");
+
+    assert_eq!(
+        columns(&tui),
+        ["Ideas", "Next", "Waiting", "Later", "Reading"]
+    );
+    assert!(status_bar(&mut tui).starts_with("Added column 'Reading'"));
+
+    // Focus "Next" (holding "Read book"), rename it and move it first.
+    tui.press("hhhR<C-u>Soon<Enter><lt>");
+
+    assert_eq!(
+        columns(&tui),
+        ["Soon", "Ideas", "Waiting", "Later", "Reading"]
+    );
+
+    // Removing asks first; any other key calls it off.
+    tui.press("D");
+
+    assert!(status_bar(&mut tui).starts_with("Remove column 'Soon'? Press D to confirm"));
+
+    tui.press("jD");
+
+    assert_eq!(columns(&tui).len(), 5);
+
+    // Confirming hands "Read book" to the column on its right.
+    tui.press("D");
+
+    assert_eq!(columns(&tui), ["Ideas", "Waiting", "Later", "Reading"]);
+    assert!(status_bar(&mut tui).starts_with("Removed column 'Soon'; moved 1 todo to 'Ideas'"));
+    assert!(tui.text().contains("Read book"));
+
+    tui.press(":column rename Someday<Enter>");
+
+    assert_eq!(columns(&tui)[0], "Someday");
+
+    tui.press(":column add someday<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("there's already a column named 'someday'"));
+
+    // `:column remove` asks the same way, and only from the backlog.
+    tui.press(":column remove<Enter>");
+
+    assert_eq!(columns(&tui).len(), 4);
+
+    tui.press("D");
+
+    assert_eq!(columns(&tui), ["Waiting", "Later", "Reading"]);
+
+    tui.press("b:column remove<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("column remove only works in the backlog view"));
+    assert_eq!(columns(&tui).len(), 3);
+}
+
+#[test]
+fn help_view() {
+    let mut tui = tui(seed_week);
+
+    tui.press("?");
+
+    assert_debug_snapshot!(tui.buffer());
+
+    tui.press("?");
+
+    assert!(!tui.text().contains("Help"));
+}
+
+#[test]
+fn complete_and_move_from_the_board() {
+    let mut tui = tui(seed_week);
+    let today = date(2025, 1, 15);
+
+    // The cursor starts on the top todo of today.
+    tui.press("s");
+
+    assert_eq!(tui.titles(ListScope::Day(today)), ["Review PR", "Pay rent"]);
+    assert!(
+        tui.titles(ListScope::Backlog)
+            .contains(&"Write report".into())
+    );
+
+    tui.press("x");
+
+    assert_eq!(tui.titles(ListScope::Day(today)), ["Pay rent", "Review PR"]);
+}
+
+#[test]
+fn quit_keys() {
+    for keys in ["q", "<Esc>", "<C-c>"] {
+        let mut tui = tui(async |_| {});
+
+        assert!(!tui.should_quit());
+
+        tui.press(keys);
+
+        assert!(tui.should_quit(), "{keys} should quit");
+    }
+}
+
+#[test]
+fn tick_follows_the_date() {
+    let mut tui = tui(seed_week);
+
+    tui.fx_clock.advance(Duration::days(1));
+    tui.tick();
+
+    assert_eq!(tui.services().today(), date(2025, 1, 16));
+    assert_snapshot!(tui.text());
+}
+
+#[test]
+fn tick_reloads_after_external_writes() {
+    let mut tui = tui(seed_week);
+    let today = date(2025, 1, 15);
+
+    // Put the cursor on "Review PR", then add a todo above it from "another
+    // shell".
+    tui.press("j");
+
+    let todos = tui.services().todos.clone();
+
+    tui.runtime
+        .block_on(todos.add("From CLI", Some(today), None))
+        .unwrap();
+
+    assert!(!tui.text().contains("From CLI"));
+
+    tui.tick();
+
+    assert!(tui.text().contains("From CLI"));
+
+    // The cursor followed "Review PR" down a row.
+    tui.press("x");
+
+    assert_eq!(
+        tui.titles(ListScope::Day(today)),
+        ["From CLI", "Write report", "Pay rent", "Review PR"]
+    );
+}
+
+/// The last line of the screen.
+fn status_bar(tui: &mut Tui) -> String {
+    tui.text().lines().last().unwrap_or_default().to_string()
+}
+
+#[test]
+fn status_bar_confirms_actions() {
+    let mut tui = tui(seed_week);
+
+    assert!(status_bar(&mut tui).ends_with("Jan 12 – Jan 18, 2025"));
+
+    tui.press("T");
+
+    assert!(status_bar(&mut tui).starts_with("Moved 'Write report' to tomorrow"));
+
+    tui.press("g");
+
+    assert!(status_bar(&mut tui).contains("g…"));
+
+    // Confirmations don't stand in the way of quitting.
+    tui.press("<Esc><Esc>");
+
+    assert!(tui.should_quit());
+}
+
+#[test]
+fn status_bar_reports_errors() {
+    let mut tui = tui(seed_week);
+
+    // Open "Write report", move to the date field and enter garbage.
+    tui.press("<Space>j<Enter><C-u>someday-ish<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("unrecognized date 'someday-ish'"));
+    assert!(
+        tui.titles(ListScope::Day(date(2025, 1, 15)))
+            .contains(&"Write report".into())
+    );
+
+    // Close the modal; the first `Esc` on the board dismisses the error.
+    tui.press("<Esc><Esc>");
+
+    assert!(!tui.should_quit(), "Esc dismisses the error first");
+    assert!(!status_bar(&mut tui).contains("unrecognized"));
+
+    tui.press("<Esc>");
+
+    assert!(tui.should_quit());
+}
+
+#[test]
+fn rollover_review_reports_unknown_dates() {
+    let mut tui = tui(async |fx| {
+        todo("Slipped").on(date(2025, 1, 14)).insert(fx).await;
+        fx.services.todos.rollover_to(fx.today()).await.unwrap();
+    });
+
+    assert!(tui.text().contains("Slipped"));
+
+    tui.press("rnot a day<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("unrecognized date 'not a day'"));
+
+    tui.press("<C-u>fri<Enter>");
+
+    assert_eq!(tui.titles(ListScope::Day(date(2025, 1, 17))), ["Slipped"]);
+}
+
+#[test]
+fn status_bar_shows_titles_cut_off_by_their_tags() {
+    // The title alone fits today's column; with its tag it doesn't.
+    let mut tui = tui(async |fx| {
+        fx.services
+            .config
+            .set("title_overflow", "truncate")
+            .await
+            .unwrap();
+
+        todo("Call the bank").tag("home").insert(fx).await;
+    });
+
+    assert!(status_bar(&mut tui).starts_with("Call the bank"));
+}
+
+#[test]
+fn jump_to_date_and_back_to_today() {
+    let mut tui = tui(seed_week);
+
+    tui.press("gd2025-03-05<Enter>");
+
+    assert!(status_bar(&mut tui).ends_with("Mar 2 – Mar 8, 2025"));
+
+    tui.press(":next fri<Enter>");
+
+    assert!(status_bar(&mut tui).ends_with("Jan 19 – Jan 25, 2025"));
+
+    tui.press("gt");
+
+    assert!(status_bar(&mut tui).ends_with("Jan 12 – Jan 18, 2025"));
+
+    // The cursor is back on today's top todo.
+    tui.press("x");
+
+    assert!(status_bar(&mut tui).starts_with("Completed 'Write report'"));
+
+    tui.press("gdwhenever<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("unrecognized date 'whenever'"));
+}
+
+#[test]
+fn command_line_runs_actions() {
+    let mut tui = tui(seed_week);
+    let today = date(2025, 1, 15);
+
+    // Complete the command name, then run it on the focused todo.
+    tui.press(":mo<Tab>fri<Enter>");
+
+    assert!(
+        tui.titles(ListScope::Day(date(2025, 1, 17)))
+            .contains(&"Write report".into())
+    );
+    assert!(
+        !tui.titles(ListScope::Day(today))
+            .contains(&"Write report".into())
+    );
+
+    // Focus stays on the row, now holding the next todo.
+    tui.press(":tag +home<Enter>");
+
+    let services = tui.harness.services();
+    let tagged: Vec<String> = tui
+        .runtime
+        .block_on(services.todos.query(&TodoQuery::new().tag("home")))
+        .unwrap()
+        .into_iter()
+        .map(|todo| todo.title)
+        .collect();
+
+    assert_eq!(tagged, ["Review PR"]);
+
+    tui.press(":week 2025-03-05<Enter>");
+
+    assert!(status_bar(&mut tui).ends_with("Mar 2 – Mar 8, 2025"));
+
+    // History brings back the last command.
+    tui.press("gt:<Up><Enter>");
+
+    assert!(status_bar(&mut tui).ends_with("Mar 2 – Mar 8, 2025"));
+
+    tui.press(":set weekstart monday<Enter>");
+
+    let config = tui.services().config.clone();
+
+    assert!(status_bar(&mut tui).ends_with("Jan 13 – Jan 19, 2025"));
+    assert_eq!(
+        tui.runtime
+            .block_on(config.load_week_start())
+            .unwrap()
+            .as_str(),
+        "monday"
+    );
+
+    tui.press(":frobnicate<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("unknown command 'frobnicate'"));
+}
+
+#[test]
+fn command_line_exports_scheduled_todos() {
+    let mut tui = tui(seed_week);
+    let path = std::env::temp_dir().join(format!("mach-{}.ics", uuid::Uuid::new_v4()));
+
+    tui.press(&format!(":export ics {}<Enter>", path.display()));
+
+    assert!(status_bar(&mut tui).starts_with("Exported 4 todo(s) to "));
+
+    let ics = std::fs::read_to_string(&path).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(ics.contains("SUMMARY:✓ Pay rent\r\n"));
+    assert!(ics.contains("SUMMARY:Dentist\r\n"));
+    assert!(!ics.contains("Plan trip"));
+
+    tui.press(":export csv<Enter>");
+
+    assert!(status_bar(&mut tui).starts_with("unsupported export format 'csv'"));
+}
+
+#[test]
+fn command_line_completion_cycles() {
+    let mut tui = tui(seed_week);
+
+    tui.press(":set <Tab>");
+
+    assert_snapshot!(tui.text());
+
+    // A unique match moves on to the value, which completes from its choices.
+    tui.press("<C-u>set wee<Tab><Tab>");
+
+    assert!(
+        tui.text()
+            .lines()
+            .last()
+            .unwrap()
+            .starts_with(":set week_start sunday")
+    );
+}
+
+#[test]
+fn monochrome_theme_draws_without_colors() {
+    let mut tui = tui(seed_week);
+
+    tui.press(":set theme monochrome<Enter>");
+
+    let buffer = tui.buffer();
+
+    assert!(
+        buffer
+            .content()
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+    );
+
+    let config = tui.services().config.clone();
+
+    assert_eq!(
+        tui.runtime.block_on(config.load_theme()).unwrap(),
+        Theme::Monochrome
+    );
+}
+
+#[test]
+fn color_overrides_apply_on_top_of_a_theme() {
+    let overrides = BTreeMap::from([("focus".to_string(), "#2aa198".to_string())]);
+    let (palette, problem) = Palette::resolve(Theme::HighContrast, &overrides, false);
+
+    assert!(problem.is_none());
+    assert_eq!(palette.focus.fg, Some(Color::Rgb(0x2a, 0xa1, 0x98)));
+    assert_eq!(palette.active, Palette::theme(Theme::HighContrast).active);
+
+    // NO_COLOR wins over both the theme and the overrides.
+    let (palette, _) = Palette::resolve(Theme::Solarized, &overrides, true);
+
+    assert_eq!(palette, Palette::theme(Theme::Monochrome));
+
+    // Bad overrides are reported while the valid ones still apply.
+    let mixed = BTreeMap::from([
+        ("chrome".to_string(), "red".to_string()),
+        ("focus".to_string(), "blurple".to_string()),
+        ("tag".to_string(), "#2aa198".to_string()),
+    ]);
+    let (palette, problem) = Palette::resolve(Theme::Default, &mixed, false);
+    let problem = problem.unwrap().to_string();
+
+    assert!(problem.contains("unknown color role 'chrome'"));
+    assert!(problem.contains("unknown color 'blurple' for focus"));
+    assert_eq!(palette.focus, Palette::theme(Theme::Default).focus);
+    assert_eq!(palette.tag.fg, Some(Color::Rgb(0x2a, 0xa1, 0x98)));
+}
+
+/// Screen cell of the first line showing `title`.
+fn cell_of(tui: &mut Tui, title: &str) -> (u16, u16) {
+    let text = tui.text();
+
+    text.lines()
+        .enumerate()
+        .find_map(|(y, line)| {
+            let byte = line.find(title)?;
+
+            Some((line[..byte].width() as u16, y as u16))
+        })
+        .unwrap_or_else(|| panic!("'{title}' not on screen:\n{text}"))
+}
+
+#[test]
+fn dragging_reorders_and_moves_todos() {
+    let mut tui = tui(async |fx| {
+        for title in ["Delta", "Charlie", "Bravo", "Alpha"] {
+            todo(title).insert(fx).await;
+        }
+
+        todo("Xray").on(date(2025, 1, 16)).insert(fx).await;
+    });
+    let today = ListScope::Day(date(2025, 1, 15));
+    let tomorrow = ListScope::Day(date(2025, 1, 16));
+
+    // Down within the column: lands in place of the todo under the pointer.
+    let (a, c) = (cell_of(&mut tui, "Alpha"), cell_of(&mut tui, "Charlie"));
+    tui.drag(a, c);
+    assert_eq!(tui.titles(today), ["Bravo", "Charlie", "Alpha", "Delta"]);
+
+    // Up to the top.
+    let (d, b) = (cell_of(&mut tui, "Delta"), cell_of(&mut tui, "Bravo"));
+    tui.drag(d, b);
+    assert_eq!(tui.titles(today), ["Delta", "Bravo", "Charlie", "Alpha"]);
+
+    // Onto another day, above the todo dropped on.
+    let (c, x) = (cell_of(&mut tui, "Charlie"), cell_of(&mut tui, "Xray"));
+    tui.drag(c, x);
+    assert_eq!(tui.titles(today), ["Delta", "Bravo", "Alpha"]);
+    assert_eq!(tui.titles(tomorrow), ["Charlie", "Xray"]);
+
+    // Below the last todo of another day: the bottom.
+    let (d, x) = (cell_of(&mut tui, "Delta"), cell_of(&mut tui, "Xray"));
+    tui.drag(d, (x.0, x.1 + 3));
+    assert_eq!(tui.titles(tomorrow), ["Charlie", "Xray", "Delta"]);
+}
+
+#[test]
+fn dragging_between_backlog_columns_keeps_the_drop_position() {
+    let mut tui = tui(async |fx| {
+        for title in ["Next one", "Next two"] {
+            let model = todo(title).backlog().insert(fx).await;
+
+            fx.services
+                .todos
+                .move_to_backlog(model.id, 1, Slot::Bottom)
+                .await
+                .unwrap();
+        }
+
+        todo("Ideas one").backlog().insert(fx).await;
+    });
+
+    tui.press("b");
+
+    let (from, to) = (
+        cell_of(&mut tui, "Ideas one"),
+        cell_of(&mut tui, "Next two"),
+    );
+    tui.drag(from, to);
+
+    assert_eq!(
+        backlog_column(&tui, 1),
+        ["Next one", "Ideas one", "Next two"]
+    );
+}
+
+#[test]
+fn todos_added_or_moved_into_a_backlog_column_land_on_top() {
+    let mut tui = tui(async |fx| {
+        for title in ["Next 3", "Next 2", "Next 1"] {
+            todo(title).column(1).insert(fx).await;
+        }
+
+        todo("Idea").backlog().insert(fx).await;
+    });
+
+    // Move the selected idea one column right.
+    tui.press("b<Enter>l");
+
+    assert_eq!(
+        backlog_column(&tui, 1),
+        ["Idea", "Next 1", "Next 2", "Next 3"]
+    );
+
+    // Drop the selection and add to the empty column after it.
+    tui.press("<Enter>l");
+
+    for title in ["First", "Second", "Third"] {
+        tui.press(&format!("a{title}<Enter>"));
+    }
+
+    assert_eq!(backlog_column(&tui, 2), ["Third", "Second", "First"]);
+}
+
+/// Titles in one backlog column, top to bottom.
+fn backlog_column(tui: &Tui, column: i64) -> Vec<String> {
+    let query = TodoQuery::new().placement(PlacementQuery::Backlog(Some(column)));
+    let services = tui.harness.services();
+
+    tui.runtime
+        .block_on(services.todos.query(&query))
+        .unwrap()
+        .into_iter()
+        .map(|todo| todo.title)
+        .collect()
+}